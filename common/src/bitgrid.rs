@@ -0,0 +1,410 @@
+use std::fmt::{Debug, Formatter, Write};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+#[inline]
+const fn words_for(bits: usize) -> usize {
+    bits.div_ceil(WORD_BITS)
+}
+
+// Rows are stored as runs of whole words, with the unused high bits of the last word of each row
+// kept clear so that rows can be compared and counted word by word.
+#[derive(Clone, Hash, Eq, PartialEq)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    row_words: usize,
+    words: Vec<u64>,
+}
+
+impl BitGrid {
+    pub fn new(width: usize, height: usize) -> Self {
+        let row_words = words_for(width);
+        Self {
+            width,
+            height,
+            row_words,
+            words: vec![0; row_words * height],
+        }
+    }
+
+    #[inline]
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    #[inline]
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    #[inline]
+    fn locate(&self, x: usize, y: usize) -> (usize, u64) {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) out of bounds"
+        );
+        (y * self.row_words + x / WORD_BITS, 1 << (x % WORD_BITS))
+    }
+
+    #[inline]
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        let (i, mask) = self.locate(x, y);
+        self.words[i] & mask != 0
+    }
+
+    #[inline]
+    pub fn set(&mut self, x: usize, y: usize, value: bool) {
+        let (i, mask) = self.locate(x, y);
+        if value {
+            self.words[i] |= mask;
+        } else {
+            self.words[i] &= !mask;
+        }
+    }
+
+    #[inline]
+    pub fn toggle(&mut self, x: usize, y: usize) {
+        let (i, mask) = self.locate(x, y);
+        self.words[i] ^= mask;
+    }
+
+    #[inline]
+    pub fn row(&self, y: usize) -> BitRow<'_> {
+        let start = y * self.row_words;
+        BitRow {
+            len: self.width,
+            words: &self.words[start..start + self.row_words],
+        }
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = BitRow<'_>> + ExactSizeIterator + '_ {
+        (0..self.height).map(|y| self.row(y))
+    }
+
+    #[inline]
+    pub fn column(&self, x: usize) -> BitColumn<'_> {
+        assert!(x < self.width, "column {x} out of bounds");
+        BitColumn { grid: self, x }
+    }
+
+    pub fn columns(&self) -> impl DoubleEndedIterator<Item = BitColumn<'_>> + ExactSizeIterator {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    // Transposes 64x64 blocks at a time, so the cost is proportional to the number of words rather
+    // than the number of cells.
+    pub fn transpose(&self) -> Self {
+        let mut transposed = Self::new(self.height, self.width);
+        let mut block = [0u64; WORD_BITS];
+
+        for by in 0..words_for(self.height) {
+            for bx in 0..self.row_words {
+                for (i, word) in block.iter_mut().enumerate() {
+                    let y = by * WORD_BITS + i;
+                    *word = if y < self.height {
+                        self.words[y * self.row_words + bx]
+                    } else {
+                        0
+                    };
+                }
+
+                transpose64(&mut block);
+
+                for (i, word) in block.iter().enumerate() {
+                    let x = bx * WORD_BITS + i;
+                    if x < self.width {
+                        transposed.words[x * transposed.row_words + by] = *word;
+                    }
+                }
+            }
+        }
+
+        transposed
+    }
+}
+
+impl Debug for BitGrid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            Debug::fmt(&row, f)?;
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+// Swaps ever smaller off-diagonal blocks until single bits have been swapped; bit `x` of word `y`
+// ends up as bit `y` of word `x`.
+fn transpose64(a: &mut [u64; WORD_BITS]) {
+    let mut j = WORD_BITS / 2;
+    let mut m = u64::MAX >> j;
+    while j != 0 {
+        let mut k = 0;
+        while k < WORD_BITS {
+            let t = ((a[k] >> j) ^ a[k + j]) & m;
+            a[k] ^= t << j;
+            a[k + j] ^= t;
+            k = (k + j + 1) & !j;
+        }
+        j >>= 1;
+        m ^= m << j;
+    }
+}
+
+#[derive(Copy, Clone, Hash, Eq, PartialEq)]
+pub struct BitRow<'a> {
+    len: usize,
+    words: &'a [u64],
+}
+
+impl<'a> BitRow<'a> {
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    #[inline]
+    pub fn get(&self, i: usize) -> bool {
+        assert!(i < self.len, "bit {i} out of bounds");
+        self.words[i / WORD_BITS] & (1 << (i % WORD_BITS)) != 0
+    }
+
+    #[inline]
+    pub fn words(&self) -> &'a [u64] {
+        self.words
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn xor_count(&self, other: &BitRow) -> usize {
+        self.zip_words(other, |a, b| a ^ b)
+            .map(|w| w.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn and_count(&self, other: &BitRow) -> usize {
+        self.zip_words(other, |a, b| a & b)
+            .map(|w| w.count_ones() as usize)
+            .sum()
+    }
+
+    pub fn xor(&self, other: &BitRow) -> BitVec {
+        BitVec {
+            len: self.len,
+            words: self.zip_words(other, |a, b| a ^ b).collect(),
+        }
+    }
+
+    pub fn and(&self, other: &BitRow) -> BitVec {
+        BitVec {
+            len: self.len,
+            words: self.zip_words(other, |a, b| a & b).collect(),
+        }
+    }
+
+    pub fn ones(&self) -> impl Iterator<Item = usize> + 'a {
+        self.words.iter().enumerate().flat_map(|(i, w)| {
+            let mut w = *w;
+            std::iter::from_fn(move || {
+                (w != 0).then(|| {
+                    let bit = w.trailing_zeros() as usize;
+                    w &= w - 1;
+                    i * WORD_BITS + bit
+                })
+            })
+        })
+    }
+
+    fn zip_words<'b>(
+        &self,
+        other: &BitRow<'b>,
+        op: fn(u64, u64) -> u64,
+    ) -> impl Iterator<Item = u64> + 'b
+    where
+        'a: 'b,
+    {
+        assert_eq!(self.len, other.len, "rows of different length");
+        std::iter::zip(self.words, other.words).map(move |(a, b)| op(*a, *b))
+    }
+
+    pub fn to_bit_vec(&self) -> BitVec {
+        BitVec {
+            len: self.len,
+            words: self.words.to_vec(),
+        }
+    }
+}
+
+impl Debug for BitRow<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        (0..self.len).try_for_each(|i| f.write_char(if self.get(i) { '#' } else { '.' }))
+    }
+}
+
+#[derive(Clone, Hash, Eq, PartialEq)]
+pub struct BitVec {
+    len: usize,
+    words: Vec<u64>,
+}
+
+impl BitVec {
+    #[inline]
+    pub fn as_row(&self) -> BitRow<'_> {
+        BitRow {
+            len: self.len,
+            words: &self.words,
+        }
+    }
+}
+
+impl Debug for BitVec {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.as_row().fmt(f)
+    }
+}
+
+// Columns are strided through the row words; transpose the grid instead when columns are compared
+// often.
+#[derive(Copy, Clone)]
+pub struct BitColumn<'a> {
+    grid: &'a BitGrid,
+    x: usize,
+}
+
+impl<'a> BitColumn<'a> {
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.grid.height
+    }
+
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.grid.height == 0
+    }
+
+    #[inline]
+    pub fn get(&self, y: usize) -> bool {
+        self.grid.get(self.x, y)
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = bool> + 'a {
+        let BitColumn { grid, x } = *self;
+        (0..grid.height).map(move |y| grid.get(x, y))
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.iter().filter(|b| *b).count()
+    }
+
+    pub fn xor_count(&self, other: &BitColumn) -> usize {
+        assert_eq!(self.len(), other.len(), "columns of different length");
+        std::iter::zip(self.iter(), other.iter())
+            .filter(|(a, b)| a != b)
+            .count()
+    }
+
+    pub fn and_count(&self, other: &BitColumn) -> usize {
+        assert_eq!(self.len(), other.len(), "columns of different length");
+        std::iter::zip(self.iter(), other.iter())
+            .filter(|(a, b)| *a && *b)
+            .count()
+    }
+}
+
+impl PartialEq for BitColumn<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl Eq for BitColumn<'_> {}
+
+impl Debug for BitColumn<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.iter()
+            .try_for_each(|b| f.write_char(if b { '#' } else { '.' }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::XorShift;
+
+    fn pseudo_random_grid(width: usize, height: usize) -> BitGrid {
+        let mut grid = BitGrid::new(width, height);
+        let mut random = XorShift::new(0x2545_f491_4f6c_dd1d);
+        for y in 0..height {
+            for x in 0..width {
+                grid.set(x, y, random.next_u64() & 1 == 1);
+            }
+        }
+        grid
+    }
+
+    #[test]
+    fn test_transpose() {
+        for (width, height) in [(1, 1), (5, 3), (64, 64), (70, 130), (200, 33)] {
+            let grid = pseudo_random_grid(width, height);
+            let transposed = grid.transpose();
+            assert_eq!((height, width), (transposed.width(), transposed.height()));
+            for y in 0..height {
+                for x in 0..width {
+                    assert_eq!(grid.get(x, y), transposed.get(y, x));
+                }
+            }
+            assert_eq!(grid, transposed.transpose());
+        }
+    }
+
+    #[test]
+    fn test_row_operations() {
+        let mut grid = BitGrid::new(100, 3);
+        for x in [0, 3, 64, 99] {
+            grid.set(x, 0, true);
+            grid.set(x, 1, true);
+        }
+        grid.set(70, 1, true);
+        grid.set(3, 2, true);
+
+        assert_eq!(4, grid.row(0).count_ones());
+        assert_eq!(1, grid.row(0).xor_count(&grid.row(1)));
+        assert_eq!(1, grid.row(0).and_count(&grid.row(2)));
+        assert_eq!(
+            vec![70],
+            grid.row(0)
+                .xor(&grid.row(1))
+                .as_row()
+                .ones()
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(vec![0, 3, 64, 99], grid.row(0).ones().collect::<Vec<_>>());
+
+        assert_ne!(grid.row(0), grid.row(1));
+        grid.toggle(70, 1);
+        assert_eq!(grid.row(0), grid.row(1));
+    }
+
+    #[test]
+    fn test_column_operations() {
+        let grid = pseudo_random_grid(40, 90);
+        let transposed = grid.transpose();
+        for x in 0..grid.width() {
+            let column = grid.column(x);
+            assert_eq!(transposed.row(x).count_ones(), column.count_ones());
+            assert_eq!(
+                transposed.row(x).xor_count(&transposed.row(0)),
+                column.xor_count(&grid.column(0))
+            );
+        }
+        assert_eq!(grid.column(3), grid.column(3));
+    }
+}
@@ -0,0 +1,367 @@
+use std::cmp::Reverse;
+use std::collections::hash_map::Entry;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+// Every state seen by a search gets a dense index; `parents` and `costs` are indexed by it.
+struct Visited<S> {
+    index: HashMap<S, usize>,
+    states: Vec<S>,
+    parents: Vec<Option<usize>>,
+}
+
+impl<S> Visited<S>
+where
+    S: Clone + Eq + Hash,
+{
+    fn new() -> Self {
+        Self {
+            index: HashMap::new(),
+            states: Vec::new(),
+            parents: Vec::new(),
+        }
+    }
+
+    fn insert(&mut self, state: S, parent: Option<usize>) -> (usize, bool) {
+        match self.index.entry(state) {
+            Entry::Occupied(e) => (*e.get(), false),
+            Entry::Vacant(e) => {
+                let i = self.states.len();
+                self.states.push(e.key().clone());
+                self.parents.push(parent);
+                e.insert(i);
+                (i, true)
+            }
+        }
+    }
+
+    fn path_to(&self, mut i: usize) -> Vec<S> {
+        let mut path = vec![self.states[i].clone()];
+        while let Some(parent) = self.parents[i] {
+            path.push(self.states[parent].clone());
+            i = parent;
+        }
+        path.reverse();
+        path
+    }
+}
+
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Vec<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut visited = Visited::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        let (i, new) = visited.insert(start, None);
+        if new {
+            queue.push_back(i);
+        }
+    }
+
+    while let Some(i) = queue.pop_front() {
+        if is_goal(&visited.states[i]) {
+            return Some(visited.path_to(i));
+        }
+        for next in neighbours(&visited.states[i].clone()) {
+            let (j, new) = visited.insert(next, Some(i));
+            if new {
+                queue.push_back(j);
+            }
+        }
+    }
+
+    None
+}
+
+pub fn bfs_distances<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+) -> HashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut distances = HashMap::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if distances.insert(start.clone(), 0).is_none() {
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((here, distance)) = queue.pop_front() {
+        for next in neighbours(&here) {
+            if let Entry::Vacant(e) = distances.entry(next) {
+                queue.push_back((e.key().clone(), distance + 1));
+                e.insert(distance + 1);
+            }
+        }
+    }
+
+    distances
+}
+
+pub fn dijkstra<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbours: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<(Vec<S>, C)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(starts, neighbours, |_| C::default(), is_goal)
+}
+
+// Nodes are never reopened once closed, so the heuristic must be consistent: it may not drop by
+// more than the cost of any step, and is zero at the goal. Otherwise the returned path may not be
+// the cheapest one.
+pub fn astar<S, C, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<(Vec<S>, C)>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let mut visited = Visited::new();
+    let mut costs = Vec::new();
+    let mut queue = BinaryHeap::new();
+    for start in starts {
+        let h = heuristic(&start);
+        let (i, new) = visited.insert(start, None);
+        if new {
+            costs.push(C::default());
+            queue.push(Reverse((h, i)));
+        }
+    }
+
+    let mut done = Vec::new();
+    while let Some(Reverse((_, i))) = queue.pop() {
+        done.resize(costs.len(), false);
+        if std::mem::replace(&mut done[i], true) {
+            continue;
+        }
+
+        let cost = costs[i];
+        if is_goal(&visited.states[i]) {
+            return Some((visited.path_to(i), cost));
+        }
+
+        for (next, step) in neighbours(&visited.states[i].clone()) {
+            let next_cost = cost + step;
+            let h = heuristic(&next);
+            let (j, new) = visited.insert(next, Some(i));
+            if done.get(j) == Some(&true) {
+                continue;
+            }
+            if new {
+                costs.push(next_cost);
+            } else if next_cost < costs[j] {
+                costs[j] = next_cost;
+                visited.parents[j] = Some(i);
+            } else {
+                continue;
+            }
+            queue.push(Reverse((next_cost + h, j)));
+        }
+    }
+
+    None
+}
+
+// Components are found by following `neighbours` from each node, so the relation should be
+// symmetric for the result to be meaningful.
+pub fn connected_components<S, I>(
+    nodes: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+) -> Vec<Vec<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut component_of = HashMap::new();
+    let mut components = Vec::<Vec<S>>::new();
+
+    for node in nodes {
+        if component_of.contains_key(&node) {
+            continue;
+        }
+
+        let c = components.len();
+        component_of.insert(node.clone(), c);
+        let mut component = vec![node];
+        let mut i = 0;
+        while i < component.len() {
+            for next in neighbours(&component[i]) {
+                if let Entry::Vacant(e) = component_of.entry(next) {
+                    component.push(e.key().clone());
+                    e.insert(c);
+                }
+            }
+            i += 1;
+        }
+        components.push(component);
+    }
+
+    components
+}
+
+// Tarjan's algorithm, kept iterative so that long paths cannot overflow the stack. Components
+// come out with the ones they lead to first, so edges only ever lead back to earlier components.
+pub fn strongly_connected_components<S, I>(
+    nodes: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+) -> Vec<Vec<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut index = HashMap::new();
+    let mut states = Vec::new();
+    let mut low = Vec::new();
+    let mut on_stack = Vec::new();
+    let mut stack = Vec::new();
+    let mut components = Vec::new();
+
+    for root in nodes {
+        if index.contains_key(&root) {
+            continue;
+        }
+
+        let mut calls = Vec::new();
+        let mut next = Some(root);
+        loop {
+            if let Some(node) = next.take() {
+                let i = states.len();
+                index.insert(node.clone(), i);
+                calls.push((i, neighbours(&node).into_iter()));
+                states.push(node);
+                low.push(i);
+                on_stack.push(true);
+                stack.push(i);
+            }
+
+            let Some((v, edges)) = calls.last_mut() else {
+                break;
+            };
+            let v = *v;
+            if let Some(w) = edges.next() {
+                match index.get(&w) {
+                    Some(&j) if on_stack[j] => low[v] = low[v].min(j),
+                    Some(_) => {}
+                    None => next = Some(w),
+                }
+                continue;
+            }
+
+            calls.pop();
+            if let Some((u, _)) = calls.last() {
+                low[*u] = low[*u].min(low[v]);
+            }
+            if low[v] == v {
+                let mut component = Vec::new();
+                while let Some(j) = stack.pop() {
+                    on_stack[j] = false;
+                    component.push(states[j].clone());
+                    if j == v {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GRID: [&str; 5] = ["S..#.", ".#.##", ".#...", ".##..", "...#G"];
+
+    fn open(x: i32, y: i32) -> bool {
+        (0..5).contains(&x)
+            && (0..5).contains(&y)
+            && GRID[y as usize].as_bytes()[x as usize] != b'#'
+    }
+
+    fn grid_neighbours(&(x, y): &(i32, i32)) -> impl Iterator<Item = (i32, i32)> {
+        [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+            .into_iter()
+            .filter(|&(x, y)| open(x, y))
+    }
+
+    #[test]
+    fn test_bfs_grid() {
+        let path = bfs([(0, 0)], grid_neighbours, |p| *p == (4, 4)).expect("path");
+        assert_eq!(Some(&(0, 0)), path.first());
+        assert_eq!(Some(&(4, 4)), path.last());
+        assert_eq!(9, path.len());
+
+        assert_eq!(None, bfs([(0, 0)], grid_neighbours, |p| *p == (4, 0)));
+    }
+
+    #[test]
+    fn test_bfs_distances() {
+        let distances = bfs_distances([(0, 0)], grid_neighbours);
+        assert_eq!(Some(&8), distances.get(&(4, 4)));
+        assert_eq!(None, distances.get(&(4, 0)));
+    }
+
+    #[test]
+    fn test_dijkstra_indexed() {
+        let edges: [&[(usize, u32)]; 4] = [&[(1, 1), (2, 4)], &[(2, 1), (3, 5)], &[(3, 1)], &[]];
+        let (path, cost) = dijkstra([0], |n| edges[*n].iter().copied(), |n| *n == 3).unwrap();
+        assert_eq!(vec![0, 1, 2, 3], path);
+        assert_eq!(3, cost);
+    }
+
+    #[test]
+    fn test_astar_matches_bfs() {
+        let (path, cost) = astar(
+            [(0, 0)],
+            |p| grid_neighbours(p).map(|p| (p, 1)),
+            |&(x, y)| (4 - x) + (4 - y),
+            |p| *p == (4, 4),
+        )
+        .expect("path");
+        assert_eq!(8, cost);
+        assert_eq!(9, path.len());
+    }
+
+    #[test]
+    fn test_connected_components() {
+        let nodes = (0..5).flat_map(|y| (0..5).map(move |x| (x, y)));
+        let components = connected_components(nodes.filter(|&(x, y)| open(x, y)), grid_neighbours);
+        assert_eq!(2, components.len());
+        assert_eq!(16, components[0].len());
+        assert_eq!(vec![(4, 0)], components[1]);
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        let edges: [&[usize]; 7] = [&[1], &[2], &[0, 3], &[4], &[5], &[3], &[6]];
+        let mut components = strongly_connected_components(0..7, |n| edges[*n].iter().copied());
+        for component in &mut components {
+            component.sort_unstable();
+        }
+        assert_eq!(vec![vec![3, 4, 5], vec![0, 1, 2], vec![6]], components);
+
+        let long = strongly_connected_components([0], |n| (*n < 100_000).then_some(n + 1));
+        assert_eq!(100_001, long.len());
+    }
+}
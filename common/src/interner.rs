@@ -0,0 +1,170 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct Id(usize);
+
+impl Id {
+    #[inline]
+    pub const fn index(self) -> usize {
+        self.0
+    }
+}
+
+impl From<Id> for usize {
+    #[inline]
+    fn from(id: Id) -> Self {
+        id.0
+    }
+}
+
+impl Debug for Id {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{}", self.0)
+    }
+}
+
+// Ids are handed out densely in order of first appearance. Names borrowed from the input are
+// stored as-is; `into_owned` detaches the interner from the input when it needs to outlive it.
+#[derive(Clone, Default)]
+pub struct Interner<'a> {
+    ids: HashMap<Cow<'a, str>, Id>,
+    names: Vec<Cow<'a, str>>,
+}
+
+impl<'a> Interner<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn intern(&mut self, name: &'a str) -> Id {
+        self.intern_cow(Cow::Borrowed(name))
+    }
+
+    pub fn intern_owned(&mut self, name: String) -> Id {
+        self.intern_cow(Cow::Owned(name))
+    }
+
+    fn intern_cow(&mut self, name: Cow<'a, str>) -> Id {
+        if let Some(id) = self.ids.get(name.as_ref()) {
+            return *id;
+        }
+        let id = Id(self.names.len());
+        self.names.push(name.clone());
+        self.ids.insert(name, id);
+        id
+    }
+
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<Id> {
+        self.ids.get(name).copied()
+    }
+
+    #[inline]
+    pub fn name(&self, id: Id) -> &str {
+        &self.names[id.0]
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (Id, &str)> + '_ {
+        self.names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (Id(i), name.as_ref()))
+    }
+
+    pub fn into_owned(self) -> Interner<'static> {
+        let names = self
+            .names
+            .into_iter()
+            .map(|name| Cow::<str>::Owned(name.into_owned()))
+            .collect::<Vec<_>>();
+        let ids = names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| (name.clone(), Id(i)))
+            .collect();
+        Interner { ids, names }
+    }
+}
+
+impl Debug for Interner<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+pub const MAX_PACKED_LEN: usize = size_of::<u64>();
+
+// Packs names of up to eight non-NUL bytes into an integer, first byte highest, so that packed
+// names order the same way as the names themselves.
+pub fn pack(name: &str) -> Option<u64> {
+    let bytes = name.as_bytes();
+    if bytes.len() > MAX_PACKED_LEN || bytes.contains(&0) {
+        return None;
+    }
+    let mut packed = [0; MAX_PACKED_LEN];
+    packed[..bytes.len()].copy_from_slice(bytes);
+    Some(u64::from_be_bytes(packed))
+}
+
+pub fn unpack(packed: u64) -> String {
+    let bytes = packed.to_be_bytes();
+    let len = bytes.iter().position(|b| *b == 0).unwrap_or(MAX_PACKED_LEN);
+    String::from_utf8_lossy(&bytes[..len]).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intern() {
+        let input = String::from("AAA BBB AAA CCC");
+        let mut interner = Interner::new();
+        let ids = input
+            .split(' ')
+            .map(|name| interner.intern(name))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            vec![0, 1, 0, 2],
+            ids.iter().map(|id| id.index()).collect::<Vec<_>>()
+        );
+        assert_eq!(3, interner.len());
+        assert_eq!("BBB", interner.name(ids[1]));
+        assert_eq!(Some(ids[3]), interner.get("CCC"));
+        assert_eq!(None, interner.get("DDD"));
+
+        let mut owned = interner.into_owned();
+        drop(input);
+        assert_eq!(
+            vec!["AAA", "BBB", "CCC"],
+            owned.iter().map(|(_, name)| name).collect::<Vec<_>>()
+        );
+        assert_eq!(ids[1], owned.intern_owned("BBB".to_string()));
+        assert_eq!(3, owned.intern_owned("DDD".to_string()).index());
+    }
+
+    #[test]
+    fn test_pack() {
+        assert_eq!(Some(0x4141_4100_0000_0000), pack("AAA"));
+        assert_eq!(None, pack("TOO LONG!"));
+        assert_eq!(None, pack("A\0B"));
+        assert!(pack("B") > pack("AA"));
+        assert!(pack("") < pack("A"));
+        for name in ["", "A", "ZZZ", "12345678"] {
+            assert_eq!(name, unpack(pack(name).unwrap()));
+        }
+    }
+}
@@ -1,5 +1,11 @@
 use std::ops::Deref;
 
+pub mod bitgrid;
+pub mod graph;
+pub mod interner;
+pub mod random;
+pub mod render;
+
 pub trait StrExt {
     fn to_i32(&self) -> i32;
 }
@@ -0,0 +1,42 @@
+// A seeded xorshift generator for building test and benchmark inputs. It is fast and repeatable,
+// which is all they need; it is no good for anything wanting real randomness.
+#[derive(Clone, Debug)]
+pub struct XorShift(u64);
+
+impl XorShift {
+    // Seeds must not be zero, which xorshift would never leave
+    pub fn new(seed: u64) -> Self {
+        assert_ne!(0, seed, "xorshift seed");
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    // A number below `n`, slightly favouring small ones unless `n` is a power of two
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeatable() {
+        let mut a = XorShift::new(0x2545_f491_4f6c_dd1d);
+        let mut b = a.clone();
+        let drawn = (0..1000).map(|_| a.below(13)).collect::<Vec<_>>();
+        assert_eq!(drawn, (0..1000).map(|_| b.below(13)).collect::<Vec<_>>());
+        assert!((0..13).all(|n| drawn.contains(&n)));
+        assert_ne!(
+            a.next_u64(),
+            XorShift::new(0x9e37_79b9_7f4a_7c15).next_u64()
+        );
+    }
+}
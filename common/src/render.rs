@@ -0,0 +1,426 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const GREY: Rgb = Rgb(96, 96, 96);
+    pub const RED: Rgb = Rgb(220, 50, 47);
+    pub const GREEN: Rgb = Rgb(133, 153, 0);
+    pub const BLUE: Rgb = Rgb(38, 139, 210);
+    pub const YELLOW: Rgb = Rgb(181, 137, 0);
+    pub const CYAN: Rgb = Rgb(42, 161, 152);
+    pub const MAGENTA: Rgb = Rgb(211, 54, 130);
+
+    fn hex(self) -> String {
+        format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Style {
+    pub glyph: char,
+    pub foreground: Rgb,
+    pub background: Rgb,
+}
+
+impl Style {
+    pub const fn new(glyph: char, foreground: Rgb) -> Self {
+        Self {
+            glyph,
+            foreground,
+            background: Rgb::BLACK,
+        }
+    }
+
+    pub const fn on(self, background: Rgb) -> Self {
+        Self { background, ..self }
+    }
+}
+
+// Overlay coordinates are in cell units: cell (x, y) covers x..x + 1 and y..y + 1, so its
+// centre is at (x + 0.5, y + 0.5).
+#[derive(Clone, Debug)]
+pub enum Shape {
+    Path(Vec<(f64, f64)>),
+    Points(Vec<(f64, f64)>),
+    Polygon(Vec<(f64, f64)>),
+}
+
+#[derive(Clone, Debug)]
+pub struct Overlay {
+    pub shape: Shape,
+    pub colour: Rgb,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Format {
+    Ansi,
+    Ppm,
+    Svg,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ansi" => Ok(Format::Ansi),
+            "ppm" => Ok(Format::Ppm),
+            "svg" => Ok(Format::Svg),
+            _ => Err(format!(
+                "unknown render format {s:?}, expected ansi, ppm or svg"
+            )),
+        }
+    }
+}
+
+impl Format {
+    // Accepts `--render`, `--render <format>` and `--render=<format>`, defaulting to ANSI when no
+    // format follows. Exits with the supported formats when the format is unknown.
+    pub fn from_args() -> Option<Format> {
+        Self::parse_args(std::env::args().skip(1)).unwrap_or_else(|e| {
+            eprintln!("{e}");
+            std::process::exit(2)
+        })
+    }
+
+    fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Option<Format>, String> {
+        let mut args = args.into_iter().peekable();
+        while let Some(arg) = args.next() {
+            if let Some(format) = arg.strip_prefix("--render=") {
+                return format.parse().map(Some);
+            }
+            if arg == "--render" {
+                return match args.peek().filter(|a| !a.starts_with("--")) {
+                    Some(format) => format.parse().map(Some),
+                    None => Ok(Some(Format::Ansi)),
+                };
+            }
+        }
+        Ok(None)
+    }
+}
+
+const CELL_SIZE: usize = 8;
+
+pub struct Canvas<F> {
+    width: usize,
+    height: usize,
+    style: F,
+    overlays: Vec<Overlay>,
+}
+
+impl<F> Canvas<F>
+where
+    F: Fn(usize, usize) -> Style,
+{
+    pub fn new(width: usize, height: usize, style: F) -> Self {
+        Self {
+            width,
+            height,
+            style,
+            overlays: Vec::new(),
+        }
+    }
+
+    pub fn overlay(mut self, shape: Shape, colour: Rgb) -> Self {
+        self.overlays.push(Overlay { shape, colour });
+        self
+    }
+
+    pub fn write(&self, format: Format, w: &mut impl Write) -> io::Result<()> {
+        match format {
+            Format::Ansi => self.write_ansi(w),
+            Format::Ppm => self.write_ppm(w, CELL_SIZE),
+            Format::Svg => self.write_svg(w, CELL_SIZE),
+        }
+    }
+
+    pub fn write_ansi(&self, w: &mut impl Write) -> io::Result<()> {
+        let mut highlighted = HashMap::new();
+        for overlay in &self.overlays {
+            for (x, y) in overlay_cells(&overlay.shape) {
+                highlighted.insert((x, y), overlay.colour);
+            }
+        }
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let style = (self.style)(x, y);
+                let Rgb(fr, fg, fb) = style.foreground;
+                let Rgb(br, bg, bb) = highlighted
+                    .get(&(x as i64, y as i64))
+                    .copied()
+                    .unwrap_or(style.background);
+                write!(
+                    w,
+                    "\x1b[38;2;{fr};{fg};{fb}m\x1b[48;2;{br};{bg};{bb}m{}",
+                    style.glyph
+                )?;
+            }
+            writeln!(w, "\x1b[0m")?;
+        }
+        Ok(())
+    }
+
+    pub fn write_ppm(&self, w: &mut impl Write, cell_size: usize) -> io::Result<()> {
+        let width = self.width * cell_size;
+        let height = self.height * cell_size;
+        let mut pixels = vec![Rgb::BLACK; width * height];
+        let mut fill = |x0: usize, y0: usize, size: usize, colour: Rgb| {
+            for y in y0..(y0 + size).min(height) {
+                pixels[y * width + x0..y * width + (x0 + size).min(width)].fill(colour);
+            }
+        };
+
+        let inset = cell_size / 4;
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let style = (self.style)(x, y);
+                fill(x * cell_size, y * cell_size, cell_size, style.background);
+                if !style.glyph.is_whitespace() {
+                    let (px, py) = (x * cell_size + inset, y * cell_size + inset);
+                    fill(px, py, cell_size - 2 * inset, style.foreground);
+                }
+            }
+        }
+
+        let to_pixel =
+            |(x, y): (f64, f64)| ((x * cell_size as f64) as i64, (y * cell_size as f64) as i64);
+        let mut plot = |(x, y): (i64, i64), colour: Rgb| {
+            if (0..width as i64).contains(&x) && (0..height as i64).contains(&y) {
+                pixels[y as usize * width + x as usize] = colour;
+            }
+        };
+        for overlay in &self.overlays {
+            match &overlay.shape {
+                Shape::Points(points) => {
+                    for &point in points {
+                        let (cx, cy) = to_pixel(point);
+                        let r = inset as i64;
+                        for y in cy - r..=cy + r {
+                            for x in cx - r..=cx + r {
+                                plot((x, y), overlay.colour);
+                            }
+                        }
+                    }
+                }
+                shape => {
+                    for (a, b) in segments(shape) {
+                        for p in line(to_pixel(a), to_pixel(b)) {
+                            plot(p, overlay.colour);
+                        }
+                    }
+                }
+            }
+        }
+
+        write!(w, "P6\n{width} {height}\n255\n")?;
+        let bytes = pixels
+            .into_iter()
+            .flat_map(|Rgb(r, g, b)| [r, g, b])
+            .collect::<Vec<_>>();
+        w.write_all(&bytes)
+    }
+
+    pub fn write_svg(&self, w: &mut impl Write, cell_size: usize) -> io::Result<()> {
+        let s = cell_size as f64;
+        writeln!(
+            w,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" font-family="monospace" font-size="{}" text-anchor="middle" dominant-baseline="central">"#,
+            self.width * cell_size,
+            self.height * cell_size,
+            s * 0.8
+        )?;
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let style = (self.style)(x, y);
+                let (px, py) = (x as f64 * s, y as f64 * s);
+                writeln!(
+                    w,
+                    r#"<rect x="{px}" y="{py}" width="{s}" height="{s}" fill="{}"/>"#,
+                    style.background.hex()
+                )?;
+                if !style.glyph.is_whitespace() {
+                    writeln!(
+                        w,
+                        r#"<text x="{}" y="{}" fill="{}">{}</text>"#,
+                        px + s / 2.0,
+                        py + s / 2.0,
+                        style.foreground.hex(),
+                        escape(style.glyph)
+                    )?;
+                }
+            }
+        }
+
+        let points = |points: &[(f64, f64)]| {
+            points
+                .iter()
+                .map(|(x, y)| format!("{},{}", x * s, y * s))
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        for overlay in &self.overlays {
+            let colour = overlay.colour.hex();
+            match &overlay.shape {
+                Shape::Path(path) => writeln!(
+                    w,
+                    r#"<polyline points="{}" fill="none" stroke="{colour}" stroke-width="{}"/>"#,
+                    points(path),
+                    s / 4.0
+                )?,
+                Shape::Polygon(polygon) => writeln!(
+                    w,
+                    r#"<polygon points="{}" fill="{colour}" fill-opacity="0.3" stroke="{colour}" stroke-width="{}"/>"#,
+                    points(polygon),
+                    s / 4.0
+                )?,
+                Shape::Points(ps) => {
+                    for (x, y) in ps {
+                        writeln!(
+                            w,
+                            r#"<circle cx="{}" cy="{}" r="{}" fill="{colour}"/>"#,
+                            x * s,
+                            y * s,
+                            s / 4.0
+                        )?;
+                    }
+                }
+            }
+        }
+        writeln!(w, "</svg>")
+    }
+}
+
+fn escape(c: char) -> String {
+    match c {
+        '<' => "&lt;".to_string(),
+        '>' => "&gt;".to_string(),
+        '&' => "&amp;".to_string(),
+        c => c.to_string(),
+    }
+}
+
+fn segments(shape: &Shape) -> Vec<((f64, f64), (f64, f64))> {
+    match shape {
+        Shape::Path(path) => path.windows(2).map(|w| (w[0], w[1])).collect(),
+        Shape::Polygon(polygon) => polygon
+            .iter()
+            .zip(polygon.iter().cycle().skip(1))
+            .map(|(a, b)| (*a, *b))
+            .collect(),
+        Shape::Points(points) => points.iter().map(|p| (*p, *p)).collect(),
+    }
+}
+
+fn overlay_cells(shape: &Shape) -> Vec<(i64, i64)> {
+    let to_cell = |(x, y): (f64, f64)| (x.floor() as i64, y.floor() as i64);
+    segments(shape)
+        .into_iter()
+        .flat_map(|(a, b)| line(to_cell(a), to_cell(b)))
+        .collect()
+}
+
+// Bresenham's line, including both end points
+fn line((mut x0, mut y0): (i64, i64), (x1, y1): (i64, i64)) -> Vec<(i64, i64)> {
+    let dx = (x1 - x0).abs();
+    let dy = -(y1 - y0).abs();
+    let sx = if x0 < x1 { 1 } else { -1 };
+    let sy = if y0 < y1 { 1 } else { -1 };
+    let mut error = dx + dy;
+    let mut points = vec![(x0, y0)];
+    while (x0, y0) != (x1, y1) {
+        let e2 = 2 * error;
+        if e2 >= dy {
+            error += dy;
+            x0 += sx;
+        }
+        if e2 <= dx {
+            error += dx;
+            y0 += sy;
+        }
+        points.push((x0, y0));
+    }
+    points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn canvas() -> Canvas<impl Fn(usize, usize) -> Style> {
+        Canvas::new(3, 2, |x, y| {
+            if (x + y) % 2 == 0 {
+                Style::new('#', Rgb::WHITE)
+            } else {
+                Style::new(' ', Rgb::WHITE).on(Rgb::BLUE)
+            }
+        })
+        .overlay(Shape::Path(vec![(0.5, 0.5), (2.5, 0.5)]), Rgb::RED)
+    }
+
+    #[test]
+    fn test_line() {
+        assert_eq!(vec![(0, 0), (1, 1), (2, 1), (3, 2)], line((0, 0), (3, 2)));
+        assert_eq!(vec![(2, 2)], line((2, 2), (2, 2)));
+    }
+
+    #[test]
+    fn test_ansi() {
+        let mut out = Vec::new();
+        canvas().write_ansi(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(2, out.lines().count());
+        assert_eq!(
+            3,
+            out.lines()
+                .next()
+                .unwrap()
+                .matches("48;2;220;50;47m")
+                .count()
+        );
+        assert!(!out.lines().nth(1).unwrap().contains("48;2;220;50;47m"));
+    }
+
+    #[test]
+    fn test_ppm() {
+        let mut out = Vec::new();
+        canvas().write_ppm(&mut out, 4).unwrap();
+        let header = b"P6\n12 8\n255\n";
+        assert!(out.starts_with(header));
+        assert_eq!(header.len() + 12 * 8 * 3, out.len());
+    }
+
+    #[test]
+    fn test_svg() {
+        let mut out = Vec::new();
+        canvas().write_svg(&mut out, 10).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(6, out.matches("<rect").count());
+        assert!(out.contains(r##"<polyline points="5,5 25,5" fill="none" stroke="#dc322f""##));
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = |a: &[&str]| Format::parse_args(a.iter().map(|s| s.to_string()));
+        assert_eq!(Ok(None), args(&[]));
+        assert_eq!(Ok(Some(Format::Ansi)), args(&["--render"]));
+        assert_eq!(Ok(Some(Format::Svg)), args(&["--render", "svg"]));
+        assert_eq!(Ok(Some(Format::Ppm)), args(&["--render=ppm"]));
+        assert_eq!(
+            Err("unknown render format \"bogus\", expected ansi, ppm or svg".to_string()),
+            args(&["--render=bogus"])
+        );
+        assert_eq!(
+            Err("unknown render format \"bogus\", expected ansi, ppm or svg".to_string()),
+            args(&["--render", "bogus"])
+        );
+        assert_eq!(Ok(Some(Format::Ansi)), args(&["--render", "--verbose"]));
+    }
+}
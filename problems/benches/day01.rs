@@ -0,0 +1,76 @@
+#![feature(test)]
+
+extern crate test;
+
+use adventofcode2023_common::random::XorShift;
+use adventofcode2023_problems::day01::{calibration_value, Vocabulary};
+use test::{black_box, Bencher};
+
+const NOISE: &[&str] = &[
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "eightwo", "oneight",
+    "x", "q", "z", "on", "tw", "thre", "ni", "ei", "sev", "1", "5", "9",
+];
+
+// Long lines built from digit words, near misses and noise, with at least one digit in each
+fn generate_input(lines: usize, words_per_line: usize) -> String {
+    let mut random = XorShift::new(0x9e37_79b9_7f4a_7c15);
+    let mut input = String::new();
+    for _ in 0..lines {
+        for i in 0..words_per_line {
+            if i == words_per_line / 2 {
+                input.push_str(&(random.below(9) + 1).to_string());
+            }
+            input.push_str(NOISE[random.below(NOISE.len())]);
+        }
+        input.push('\n');
+    }
+    input
+}
+
+// The suffix-by-suffix scan the automaton replaced, trying every word at every position of the
+// line, for `naive` to time against the automaton
+fn naive_calibration_value(input: &str, table: &[(&str, i32)]) -> i32 {
+    input
+        .lines()
+        .map(|line| {
+            let mut it = str_tails(line).filter_map(|tail| {
+                table
+                    .iter()
+                    .find(|(n, _)| tail.starts_with(n))
+                    .map(|(_, v)| *v)
+            });
+            let tens = it.next().expect("digit");
+            let ones = it.last().unwrap_or(tens);
+            tens * 10 + ones
+        })
+        .sum()
+}
+
+fn str_tails(mut s: &str) -> impl Iterator<Item = &str> + '_ {
+    std::iter::from_fn(move || match s {
+        "" => None,
+        next => {
+            s = &next[1..];
+            Some(next)
+        }
+    })
+}
+
+#[bench]
+fn automaton(b: &mut Bencher) {
+    let input = generate_input(1_000, 200);
+    let vocabulary = Vocabulary::part2();
+    b.iter(|| calibration_value(black_box(&input), &vocabulary));
+}
+
+#[bench]
+fn naive(b: &mut Bencher) {
+    let input = generate_input(1_000, 200);
+    let vocabulary = Vocabulary::part2();
+    let table = vocabulary.words().collect::<Vec<_>>();
+    assert_eq!(
+        Ok(naive_calibration_value(&input, &table).into()),
+        calibration_value(&input, &vocabulary)
+    );
+    b.iter(|| naive_calibration_value(black_box(&input), &table));
+}
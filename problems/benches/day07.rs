@@ -0,0 +1,110 @@
+#![feature(test)]
+
+extern crate test;
+
+use adventofcode2023_common::random::XorShift;
+use adventofcode2023_problems::day07::{Hand, Rules};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use test::{black_box, Bencher};
+
+fn generate_hands(n: usize) -> Vec<(Hand, usize)> {
+    let mut random = XorShift::new(0x9e37_79b9_7f4a_7c15);
+    (0..n)
+        .map(|_| {
+            (
+                (0..5).map(|_| random.below(13)).collect(),
+                random.below(1000),
+            )
+        })
+        .collect()
+}
+
+fn winnings(mut keyed: Vec<(u128, usize)>) -> usize {
+    keyed.sort_unstable();
+    keyed
+        .into_iter()
+        .enumerate()
+        .map(|(rank, (_, bid))| (rank + 1) * bid)
+        .sum()
+}
+
+fn sort_by_keys(hands: &[(Hand, usize)], rules: &Rules) -> usize {
+    winnings(
+        hands
+            .iter()
+            .map(|(hand, bid)| (rules.sort_key(hand), *bid))
+            .collect(),
+    )
+}
+
+// The comparison the packed keys replaced, classifying both hands with a `HashMap` every time
+// two hands are compared, for `hash_map_comparisons`. Jokers are card 0.
+fn hand_type(hand: &Hand, jokers_wild: bool) -> usize {
+    let mut map = HashMap::<usize, usize>::new();
+    for card in hand {
+        *map.entry(*card).or_default() += 1;
+    }
+    let jokers = if jokers_wild {
+        map.remove(&0).unwrap_or(0)
+    } else {
+        0
+    };
+    if jokers == 5 {
+        return 6;
+    }
+
+    let mut map = map.into_iter().collect::<Vec<_>>();
+    map.sort_unstable_by_key(|(c, n)| (usize::MAX - *n, usize::MAX - c));
+    map[0].1 += jokers;
+    match map[..] {
+        [(_, 5), ..] => 6,
+        [(_, 4), ..] => 5,
+        [(_, 3), (_, 2), ..] => 4,
+        [(_, 3), ..] => 3,
+        [(_, 2), (_, 2), ..] => 2,
+        [(_, 2), ..] => 1,
+        _ => 0,
+    }
+}
+
+fn sort_by_comparisons(hands: &[(Hand, usize)], jokers_wild: bool) -> usize {
+    let mut hands = hands.to_vec();
+    hands.sort_unstable_by(|(a, bid_a), (b, bid_b)| -> Ordering {
+        hand_type(a, jokers_wild)
+            .cmp(&hand_type(b, jokers_wild))
+            .then_with(|| a.cmp(b))
+            .then_with(|| bid_a.cmp(bid_b))
+    });
+    hands
+        .into_iter()
+        .enumerate()
+        .map(|(rank, (_, bid))| (rank + 1) * bid)
+        .sum()
+}
+
+#[bench]
+fn sort_keys_million_hands(b: &mut Bencher) {
+    let hands = generate_hands(1_000_000);
+    let rules = Rules::part2();
+    b.iter(|| sort_by_keys(black_box(&hands), &rules));
+}
+
+// The baseline is too slow for a million hands, so both are also compared on fewer
+#[bench]
+fn sort_keys(b: &mut Bencher) {
+    let hands = generate_hands(100_000);
+    let rules = Rules::part2();
+    b.iter(|| sort_by_keys(black_box(&hands), &rules));
+}
+
+#[bench]
+fn hash_map_comparisons(b: &mut Bencher) {
+    let hands = generate_hands(100_000);
+    let rules = Rules::part2();
+    assert_eq!(
+        sort_by_keys(&hands, &rules),
+        sort_by_comparisons(&hands, true)
+    );
+    b.iter(|| sort_by_comparisons(black_box(&hands), true));
+}
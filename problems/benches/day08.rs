@@ -0,0 +1,95 @@
+#![feature(test)]
+
+extern crate test;
+
+use adventofcode2023_common::random::XorShift;
+use adventofcode2023_problems::day08::{parse, Cycle, Input, Jumps, Step};
+use test::{black_box, Bencher};
+
+// Every three-letter name, each leading to two random others, with the ghosts starting on the
+// names ending in "AA"
+fn generate_network(instructions: usize) -> String {
+    let mut random = XorShift::new(0x9e37_79b9_7f4a_7c15);
+    let names = (0..26 * 26 * 26)
+        .map(|i| {
+            [i / 676, i / 26 % 26, i % 26]
+                .map(|c| (b'A' + c as u8) as char)
+                .iter()
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>();
+    let mut network = (0..instructions)
+        .map(|_| if random.below(2) == 0 { 'L' } else { 'R' })
+        .collect::<String>();
+    network.push('\n');
+    for name in &names {
+        let left = &names[random.below(names.len())];
+        let right = &names[random.below(names.len())];
+        network.push_str(&format!("\n{name} = ({left}, {right})"));
+    }
+    network
+}
+
+fn parse_network(network: &str) -> Input<'_> {
+    parse(network, |s| s.ends_with("AA"), |s| s.ends_with('Z')).unwrap()
+}
+
+fn cycles_by_blocks(input: &Input) -> Vec<Cycle> {
+    let jumps = Jumps::new(&input.instructions, &input.nodes);
+    input
+        .starts
+        .iter()
+        .map(|start| jumps.cycle(*start))
+        .collect()
+}
+
+// The walk the jump table replaced, one step at a time until a (node, instruction) state repeats,
+// which `step_by_step_cycles` also checks the jump table against
+fn cycles_by_steps(input: &Input) -> Vec<Cycle> {
+    let len = input.instructions.len();
+    input
+        .starts
+        .iter()
+        .map(|start| {
+            let mut seen = vec![usize::MAX; input.nodes.len() * len];
+            let mut terminals = Vec::new();
+            let mut here = *start;
+            let mut step = 0;
+            loop {
+                let i = step % len;
+                let state = here * len + i;
+                if seen[state] != usize::MAX {
+                    return Cycle {
+                        prefix: seen[state],
+                        period: step - seen[state],
+                        terminals,
+                    };
+                }
+                seen[state] = step;
+                if input.nodes[here].1 {
+                    terminals.push(step);
+                }
+                here = match input.instructions[i] {
+                    Step::Left => input.nodes[here].0[0],
+                    Step::Right => input.nodes[here].0[1],
+                };
+                step += 1;
+            }
+        })
+        .collect()
+}
+
+#[bench]
+fn jump_table_cycles(b: &mut Bencher) {
+    let network = generate_network(300);
+    let input = parse_network(&network);
+    b.iter(|| cycles_by_blocks(black_box(&input)));
+}
+
+#[bench]
+fn step_by_step_cycles(b: &mut Bencher) {
+    let network = generate_network(300);
+    let input = parse_network(&network);
+    assert_eq!(cycles_by_blocks(&input), cycles_by_steps(&input));
+    b.iter(|| cycles_by_steps(black_box(&input)));
+}
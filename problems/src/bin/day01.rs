@@ -0,0 +1,43 @@
+use adventofcode2023_problems::day01::{
+    calibration_value, part1, part2, write_explanation, Vocabulary, INPUT,
+};
+use std::io::{stdout, IsTerminal};
+
+// `--vocabulary <file>` additionally sums the input with the tokens from a `word=value` file, and
+// `--ignore-case` matches those tokens regardless of case. `--explain` shows the tokens picked
+// on every line, highlighted when stdout is a terminal.
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let explain = args.iter().any(|a| a == "--explain");
+    let ignore_case = args.iter().any(|a| a == "--ignore-case");
+    let vocabulary = args
+        .iter()
+        .position(|a| a == "--vocabulary")
+        .map(|i| args.get(i + 1).expect("vocabulary file"));
+
+    let vocabulary = vocabulary.map(|path| {
+        let vocabulary = std::fs::read_to_string(path).expect("readable vocabulary");
+        Vocabulary::parse(&vocabulary)
+            .and_then(|v| v.ignore_case(ignore_case))
+            .unwrap_or_else(|e| panic!("{path}: {e}"))
+    });
+
+    if explain {
+        let vocabularies = match &vocabulary {
+            Some(vocabulary) => vec![vocabulary.clone()],
+            None => vec![Vocabulary::part1(), Vocabulary::part2()],
+        };
+        for vocabulary in vocabularies {
+            let colour = stdout().is_terminal();
+            write_explanation(INPUT, &vocabulary, colour, &mut stdout().lock())
+                .expect("explanation");
+        }
+    }
+
+    eprintln!("{}", part1(INPUT));
+    eprintln!("{}", part2(INPUT));
+    if let Some(vocabulary) = vocabulary {
+        let value = calibration_value(INPUT, &vocabulary).unwrap_or_else(|e| panic!("{e}"));
+        eprintln!("{value}");
+    }
+}
@@ -0,0 +1,46 @@
+use adventofcode2023_problems::day02::{
+    bags_with_total, conflicts, minimal_bag, parse, part1, part2, possible_games, rank_bags, Bag,
+    INPUT, PUZZLE_BAG,
+};
+
+// `--bag <file>` additionally sums the ids of the games possible with the bag contents in the
+// file, written like a single draw (`12 red, 13 green, 14 blue`). `--infer` prints what the game
+// log says about the bag: the smallest bag allowing every game, the games conflicting with the
+// given bag, and the most likely bags holding as many cubes as it does.
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let infer = args.iter().any(|a| a == "--infer");
+    let bag_file = args
+        .iter()
+        .position(|a| a == "--bag")
+        .map(|i| args.get(i + 1).expect("bag file"))
+        .map(|path| std::fs::read_to_string(path).expect("readable bag"));
+    let bag = Bag::parse(bag_file.as_deref().unwrap_or(PUZZLE_BAG))
+        .unwrap_or_else(|e| panic!("bag: {e}"));
+
+    eprintln!("{}", part1(INPUT).unwrap_or_else(|e| panic!("{e}")));
+    eprintln!("{}", part2(INPUT).unwrap_or_else(|e| panic!("{e}")));
+    if bag_file.is_some() {
+        let possible = possible_games(INPUT, &bag).unwrap_or_else(|e| panic!("{e}"));
+        eprintln!("{possible}");
+    }
+
+    if infer {
+        let games = parse(INPUT).unwrap_or_else(|e| panic!("{e}"));
+        let minimum = minimal_bag(&games);
+        println!("minimal bag: {minimum}");
+        println!("conflicts with {bag}:");
+        for conflict in conflicts(&games, &bag) {
+            println!("  game {}: {} too many", conflict.game, conflict.excess);
+        }
+
+        let total = bag.iter().map(|(_, n)| n).sum::<i32>();
+        println!("most likely bags of {total} cubes:");
+        for (bag, log_likelihood) in rank_bags(&games, bags_with_total(&minimum, total))
+            .into_iter()
+            .take(5)
+        {
+            println!("  {bag}: log likelihood {log_likelihood:.3}");
+        }
+    }
+}
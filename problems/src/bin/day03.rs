@@ -0,0 +1,14 @@
+use adventofcode2023_problems::day03::{part1, part2, scan, INPUT};
+use std::io::stdin;
+
+// `--stream` answers both parts for a schematic read from stdin, a row at a time.
+fn main() {
+    if std::env::args().skip(1).any(|a| a == "--stream") {
+        let totals = scan(stdin().lock()).expect("readable schematic");
+        eprintln!("{}", totals.part_numbers);
+        eprintln!("{}", totals.gear_ratios);
+        return;
+    }
+    eprintln!("{}", part1(INPUT));
+    eprintln!("{}", part2(INPUT));
+}
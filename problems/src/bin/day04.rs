@@ -0,0 +1,42 @@
+use adventofcode2023_problems::day04::{
+    parse, part1, part2, total_cards, total_points, write_trace, Edge, Rules, Scoring, Window,
+    INPUT,
+};
+use std::io::stdout;
+
+// `--linear`, `--window <n>` and `--wrap` change the scoring and copy rules, and `--trace` prints
+// each card's matches, copies and points under those rules.
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let trace = args.iter().any(|a| a == "--trace");
+    let mut rules = Rules::PUZZLE;
+    if args.iter().any(|a| a == "--linear") {
+        rules.scoring = Scoring::Linear;
+    }
+    if args.iter().any(|a| a == "--wrap") {
+        rules.edge = Edge::Wrap;
+    }
+    if let Some(i) = args.iter().position(|a| a == "--window") {
+        let size = args.get(i + 1).and_then(|n| n.parse().ok());
+        rules.window = Window::Fixed(size.expect("window size"));
+    }
+
+    if rules == Rules::PUZZLE && !trace {
+        eprintln!("{}", part1(INPUT));
+        eprintln!("{}", part2(INPUT));
+        return;
+    }
+
+    let cards = parse(INPUT).unwrap_or_else(|e| panic!("{e}"));
+    if trace {
+        write_trace(&cards, &rules, &mut stdout().lock()).expect("trace");
+    }
+    eprintln!(
+        "{}",
+        total_points(&cards, &rules).unwrap_or_else(|e| panic!("{e}"))
+    );
+    eprintln!(
+        "{}",
+        total_cards(&cards, &rules).unwrap_or_else(|e| panic!("{e}"))
+    );
+}
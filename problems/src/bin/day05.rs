@@ -0,0 +1,45 @@
+use adventofcode2023_problems::day05::{parse, parse_strict, part1, part2, seeds_as_ranges, INPUT};
+
+// `--to <category>` lists the values of that category for the seed ranges. `--validate` reports
+// problems with the maps and the gaps they pass through unchanged, and `--strict` refuses maps
+// that are ambiguous. Otherwise entries left out of the maps are still warned about.
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let strict = args.iter().any(|a| a == "--strict");
+    let almanac = if strict {
+        parse_strict(INPUT)
+    } else {
+        parse(INPUT)
+    };
+    let almanac = almanac.unwrap_or_else(|e| panic!("{e}"));
+
+    let validate = args.iter().any(|a| a == "--validate");
+    if !validate {
+        for table in &almanac.tables {
+            for issue in table.validate().iter().filter(|i| i.is_skipped()) {
+                eprintln!("warning: {}: {issue}", table.name());
+            }
+        }
+    }
+    if validate {
+        for table in &almanac.tables {
+            println!("{}", table.name());
+            for issue in table.validate() {
+                println!("  {issue}");
+            }
+            for gap in table.gaps() {
+                println!("  {gap:?} passes through");
+            }
+        }
+    }
+    if let Some(i) = args.iter().position(|a| a == "--to") {
+        let to = args.get(i + 1).expect("category");
+        let seeds = seeds_as_ranges(&almanac.seeds).unwrap_or_else(|e| panic!("{e}"));
+        match almanac.convert("seed", to, &seeds) {
+            Ok(ranges) => ranges.iter().for_each(|r| println!("{r:?}")),
+            Err(e) => eprintln!("{e}"),
+        }
+    }
+    eprintln!("{}", part1(INPUT));
+    eprintln!("{}", part2(INPUT));
+}
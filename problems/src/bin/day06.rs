@@ -0,0 +1,42 @@
+use adventofcode2023_problems::day06::{parse, parse_races2, part1, part2, report, Model, INPUT};
+
+// `--charge-rate <n>` and `--top-speed <n>`, or `--polynomial <c0,c1,...>`, change how the boat
+// picks up speed, and report the best hold and the winning holds for each race under that model.
+// None of them may be negative, and polynomials whose distance may peak twice in a race are
+// refused.
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let value = |flag: &str| {
+        let i = args.iter().position(|a| a == flag)?;
+        Some(args.get(i + 1).unwrap_or_else(|| panic!("{flag} value")))
+    };
+    let number = |flag: &str| value(flag).map(|n| n.parse::<i64>().expect("number"));
+
+    let model = match value("--polynomial") {
+        Some(coefficients) => Some(Model::Polynomial(
+            coefficients
+                .split(',')
+                .map(|c| c.trim().parse().expect("coefficient"))
+                .collect(),
+        )),
+        None if value("--charge-rate").is_some() || value("--top-speed").is_some() => {
+            Some(Model::Charged {
+                charge_rate: number("--charge-rate").unwrap_or(1),
+                top_speed: number("--top-speed"),
+            })
+        }
+        None => None,
+    };
+
+    if let Some(model) = model {
+        model.validate().unwrap_or_else(|e| panic!("{e}"));
+        let mut races = parse(INPUT).expect("races");
+        races.extend(parse_races2(INPUT));
+        for race in report(&races, &model).unwrap_or_else(|e| panic!("{e}")) {
+            println!("{race}");
+        }
+        return;
+    }
+    eprintln!("{}", part1(INPUT));
+    eprintln!("{}", part2(INPUT));
+}
@@ -0,0 +1,27 @@
+use adventofcode2023_problems::day07::{
+    part1, part2, report, write_json, write_table, Rules, INPUT,
+};
+use std::io::stdout;
+
+// `--report <table|json>` lists the hands in rank order with their types, winnings and what the
+// jokers were played as. `--jacks` reports with jacks instead of jokers.
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    if let Some(i) = args.iter().position(|a| a == "--report") {
+        let rules = if args.iter().any(|a| a == "--jacks") {
+            Rules::part1()
+        } else {
+            Rules::part2()
+        };
+        let ranking = report(INPUT, &rules).unwrap_or_else(|e| panic!("{e}"));
+        let out = &mut stdout().lock();
+        match args.get(i + 1).map(String::as_str) {
+            Some("json") => write_json(&ranking, out),
+            Some("table") | None => write_table(&ranking, out),
+            Some(format) => panic!("unknown report format `{format}`"),
+        }
+        .expect("report");
+    }
+    eprintln!("{}", part1(INPUT));
+    eprintln!("{}", part2(INPUT));
+}
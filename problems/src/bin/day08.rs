@@ -0,0 +1,20 @@
+use adventofcode2023_problems::day08::{parse, part1, part2, INPUT};
+use std::io::stdout;
+
+// `--dot` writes the ghosts' network as Graphviz DOT, and `--analyse` describes its components
+// and each ghost's cycle.
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let network =
+        parse(INPUT, |s| s.ends_with('A'), |s| s.ends_with('Z')).unwrap_or_else(|e| panic!("{e}"));
+    if args.iter().any(|a| a == "--dot") {
+        network.write_dot(&mut stdout().lock()).expect("dot");
+    }
+    if args.iter().any(|a| a == "--analyse") {
+        network
+            .write_analysis(&mut stdout().lock())
+            .expect("analysis");
+    }
+    eprintln!("{}", part1(INPUT));
+    eprintln!("{}", part2(INPUT));
+}
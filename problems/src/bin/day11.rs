@@ -0,0 +1,13 @@
+use adventofcode2023_common::render::Format;
+use adventofcode2023_problems::day11::{part1, part2, render, INPUT};
+use std::io::stdout;
+
+fn main() {
+    if let Some(format) = Format::from_args() {
+        render(INPUT)
+            .write(format, &mut stdout().lock())
+            .expect("render");
+    }
+    eprintln!("{}", part1(INPUT));
+    eprintln!("{}", part2(INPUT));
+}
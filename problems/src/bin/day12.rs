@@ -0,0 +1,6 @@
+use adventofcode2023_problems::day12::{part1, part2, INPUT};
+
+fn main() {
+    eprintln!("{}", part1(INPUT));
+    eprintln!("{}", part2(INPUT));
+}
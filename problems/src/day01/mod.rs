@@ -0,0 +1,526 @@
+use std::fmt::{Display, Formatter};
+use std::io::{self, Write};
+
+pub const INPUT: &str = include_str!("input.txt");
+
+pub fn part1(input: &str) -> i64 {
+    calibration_value(input, &Vocabulary::part1()).unwrap_or_else(|e| panic!("{e}"))
+}
+
+pub fn part2(input: &str) -> i64 {
+    calibration_value(input, &Vocabulary::part2()).unwrap_or_else(|e| panic!("{e}"))
+}
+
+// Every line needs a digit; `explain` lists the lines without one.
+pub fn calibration_value(input: &str, vocabulary: &Vocabulary) -> Result<i64, String> {
+    parse(input, vocabulary).try_fold(0i64, |total, value| {
+        total
+            .checked_add(value?)
+            .ok_or_else(|| "the calibration values overflow".to_string())
+    })
+}
+
+pub fn parse<'a>(
+    input: &'a str,
+    vocabulary: &Vocabulary,
+) -> impl Iterator<Item = Result<i64, String>> + 'a {
+    let matcher = DigitMatcher::new(vocabulary);
+    input.lines().enumerate().map(move |(i, line)| {
+        parse_digits(line, &matcher).map_err(|e| format!("line {}: {e}", i + 1))
+    })
+}
+
+pub fn parse_digits(s: &str, matcher: &DigitMatcher) -> Result<i64, String> {
+    let (Some(first), Some(last)) = (matcher.find_first(s), matcher.find_last(s)) else {
+        return Err(format!("no digit in {s:?}"));
+    };
+    concat_digits(first.value, last.value)
+}
+
+pub fn explain<'a>(
+    input: &'a str,
+    vocabulary: &Vocabulary,
+) -> impl Iterator<Item = Explanation<'a>> + 'a {
+    let matcher = DigitMatcher::new(vocabulary);
+    input.lines().enumerate().map(move |(i, line)| Explanation {
+        line_number: i + 1,
+        line,
+        digits: matcher.find_first(line).zip(matcher.find_last(line)),
+        colour: false,
+    })
+}
+
+// `colour` highlights the tokens with ANSI escapes
+pub fn write_explanation(
+    input: &str,
+    vocabulary: &Vocabulary,
+    colour: bool,
+    w: &mut impl Write,
+) -> io::Result<()> {
+    let mut total = Some(0i64);
+    let mut without_digits = Vec::new();
+    for explanation in explain(input, vocabulary) {
+        let explanation = Explanation {
+            colour,
+            ..explanation
+        };
+        writeln!(w, "{explanation}")?;
+        match explanation.value() {
+            Ok(Some(value)) => total = total.and_then(|t| t.checked_add(value)),
+            Ok(None) => without_digits.push(explanation.line_number.to_string()),
+            Err(_) => total = None,
+        }
+    }
+    match total {
+        Some(total) => writeln!(w, "total: {total}")?,
+        None => writeln!(w, "total: overflows")?,
+    }
+    if !without_digits.is_empty() {
+        writeln!(w, "lines without digits: {}", without_digits.join(", "))?;
+    }
+    Ok(())
+}
+
+pub struct Explanation<'a> {
+    pub line_number: usize,
+    pub line: &'a str,
+    pub digits: Option<(Match, Match)>,
+    pub colour: bool,
+}
+
+impl Explanation<'_> {
+    pub fn value(&self) -> Result<Option<i64>, String> {
+        self.digits
+            .map(|(first, last)| concat_digits(first.value, last.value))
+            .transpose()
+    }
+
+    fn token(&self, m: &Match) -> String {
+        format!("{:?}@{}..{}", &self.line[m.start..m.end], m.start, m.end)
+    }
+
+    // The first token in green, the last in cyan and any overlap between them in yellow
+    fn highlighted(&self, (first, last): &(Match, Match)) -> String {
+        if !self.colour {
+            return self.line.to_string();
+        }
+        let mut bounds = vec![
+            0,
+            first.start,
+            first.end,
+            last.start,
+            last.end,
+            self.line.len(),
+        ];
+        bounds.sort_unstable();
+        bounds.dedup();
+
+        bounds
+            .windows(2)
+            .map(|w| {
+                let in_first = first.start <= w[0] && w[1] <= first.end;
+                let in_last = last.start <= w[0] && w[1] <= last.end;
+                let segment = &self.line[w[0]..w[1]];
+                match (in_first, in_last) {
+                    (true, true) => format!("\x1b[1;33m{segment}\x1b[0m"),
+                    (true, false) => format!("\x1b[1;32m{segment}\x1b[0m"),
+                    (false, true) => format!("\x1b[1;36m{segment}\x1b[0m"),
+                    (false, false) => segment.to_string(),
+                }
+            })
+            .collect()
+    }
+}
+
+impl Display for Explanation<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.digits {
+            Some(digits @ (first, last)) => write!(
+                f,
+                "{:>5}: {:>4}  first {}  last {}  {}",
+                self.line_number,
+                concat_digits(first.value, last.value).map_or_else(|e| e, |v| v.to_string()),
+                self.token(first),
+                self.token(last),
+                self.highlighted(digits)
+            ),
+            None => write!(f, "{:>5}:    -  no digit  {}", self.line_number, self.line),
+        }
+    }
+}
+
+// Written side by side, so that multi-digit tokens such as `twenty` keep all of their digits
+fn concat_digits(first: i32, last: i32) -> Result<i64, String> {
+    let digits = last.checked_ilog10().unwrap_or(0) + 1;
+    i64::from(first)
+        .checked_mul(10i64.pow(digits))
+        .and_then(|value| value.checked_add(last.into()))
+        .ok_or_else(|| format!("{first} followed by {last} overflows"))
+}
+
+// Tokens and the values they stand for, read from `word=value` lines. Blank lines and lines
+// starting with `#` are skipped.
+#[derive(Clone, Debug)]
+pub struct Vocabulary {
+    words: Vec<(String, i32)>,
+    ignore_case: bool,
+}
+
+impl Vocabulary {
+    pub fn part1() -> Self {
+        Self::parse(include_str!("part1_vocabulary.txt")).expect("part 1 vocabulary")
+    }
+
+    pub fn part2() -> Self {
+        Self::parse(include_str!("part2_vocabulary.txt")).expect("part 2 vocabulary")
+    }
+
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let words = s
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+            .map(|(i, line)| {
+                let (word, value) = line
+                    .split_once('=')
+                    .ok_or_else(|| format!("line {}: expected word=value", i + 1))?;
+                let value = value
+                    .trim()
+                    .parse::<i32>()
+                    .ok()
+                    .filter(|v| *v >= 0)
+                    .ok_or_else(|| format!("line {}: invalid value {value:?}", i + 1))?;
+                match word.trim() {
+                    "" => Err(format!("line {}: empty word", i + 1)),
+                    word => Ok((word.to_string(), value)),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Self {
+            words,
+            ignore_case: false,
+        }
+        .checked()
+    }
+
+    pub fn ignore_case(self, ignore_case: bool) -> Result<Self, String> {
+        Self {
+            ignore_case,
+            ..self
+        }
+        .checked()
+    }
+
+    fn checked(self) -> Result<Self, String> {
+        let mut seen = std::collections::HashMap::new();
+        for (word, value) in self.words() {
+            let key = self.normalize(word);
+            if let Some(previous) = seen.insert(key, value) {
+                if previous != value {
+                    return Err(format!("{word:?} is both {previous} and {value}"));
+                }
+            }
+        }
+        Ok(self)
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = (&str, i32)> + '_ {
+        self.words
+            .iter()
+            .map(|(word, value)| (word.as_str(), *value))
+    }
+
+    fn normalize(&self, word: &str) -> Vec<u8> {
+        if self.ignore_case {
+            word.bytes().map(|c| c.to_ascii_lowercase()).collect()
+        } else {
+            word.bytes().collect()
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Match {
+    pub start: usize,
+    pub end: usize,
+    pub value: i32,
+}
+
+// The digit table compiled into two Aho-Corasick automata: one over the words as written for
+// finding the first digit, and one over the reversed words for scanning lines backwards to find
+// the last digit. Overlapping words such as `eightwo` are found by both.
+pub struct DigitMatcher {
+    forward: Automaton,
+    reverse: Automaton,
+    ignore_case: bool,
+}
+
+impl DigitMatcher {
+    pub fn new(vocabulary: &Vocabulary) -> Self {
+        let words = || {
+            vocabulary
+                .words()
+                .map(|(w, v)| (vocabulary.normalize(w), v))
+        };
+        let forward = Automaton::new(words());
+        let reverse = Automaton::new(words().map(|(mut w, v)| {
+            w.reverse();
+            (w, v)
+        }));
+        Self {
+            forward,
+            reverse,
+            ignore_case: vocabulary.ignore_case,
+        }
+    }
+
+    #[inline]
+    fn normalize(&self, c: u8) -> u8 {
+        if self.ignore_case {
+            c.to_ascii_lowercase()
+        } else {
+            c
+        }
+    }
+
+    // The match starting earliest, preferring the longest word when several start at the same
+    // position.
+    pub fn find_first(&self, s: &str) -> Option<Match> {
+        let mut best: Option<Match> = None;
+        let mut state = 0;
+        for (i, c) in s.bytes().enumerate() {
+            if best.is_some_and(|m| i >= m.start + self.forward.max_len) {
+                break;
+            }
+            state = self.forward.next(state, self.normalize(c));
+            if let Some((len, value)) = self.forward.outputs[state] {
+                let start = i + 1 - len;
+                if best.is_none_or(|m| start < m.start || (start == m.start && i + 1 > m.end)) {
+                    best = Some(Match {
+                        start,
+                        end: i + 1,
+                        value,
+                    });
+                }
+            }
+        }
+        best
+    }
+
+    // The match starting last, preferring the longest word when several start at the same
+    // position.
+    pub fn find_last(&self, s: &str) -> Option<Match> {
+        let mut state = 0;
+        for (i, c) in s.bytes().rev().enumerate() {
+            state = self.reverse.next(state, self.normalize(c));
+            if let Some((len, value)) = self.reverse.outputs[state] {
+                let start = s.len() - 1 - i;
+                return Some(Match {
+                    start,
+                    end: start + len,
+                    value,
+                });
+            }
+        }
+        None
+    }
+}
+
+struct Automaton {
+    transitions: Vec<[usize; 256]>,
+    // The longest word that is a suffix of the bytes leading to each state
+    outputs: Vec<Option<(usize, i32)>>,
+    max_len: usize,
+}
+
+impl Automaton {
+    fn new(words: impl Iterator<Item = (Vec<u8>, i32)>) -> Self {
+        let mut transitions = vec![[0; 256]];
+        let mut outputs = vec![None];
+        let mut depths = vec![0];
+        let mut max_len = 0;
+
+        for (word, value) in words {
+            let mut state = 0;
+            for c in &word {
+                state = match transitions[state][*c as usize] {
+                    0 => {
+                        transitions.push([0; 256]);
+                        outputs.push(None);
+                        depths.push(depths[state] + 1);
+                        let next = transitions.len() - 1;
+                        transitions[state][*c as usize] = next;
+                        next
+                    }
+                    next => next,
+                };
+            }
+            if outputs[state].is_none() {
+                outputs[state] = Some((word.len(), value));
+            }
+            max_len = max_len.max(word.len());
+        }
+
+        // Breadth first, so that the failure state of every state is complete before it is used
+        let mut failures = vec![0; transitions.len()];
+        let mut queue = std::collections::VecDeque::from([0]);
+        while let Some(state) = queue.pop_front() {
+            for (c, next) in transitions[state].into_iter().enumerate() {
+                if next != 0 && depths[next] == depths[state] + 1 {
+                    let failure = if state == 0 {
+                        0
+                    } else {
+                        transitions[failures[state]][c]
+                    };
+                    failures[next] = failure;
+                    if outputs[next].is_none() {
+                        outputs[next] = outputs[failure];
+                    }
+                    queue.push_back(next);
+                } else {
+                    transitions[state][c] = transitions[failures[state]][c];
+                }
+            }
+        }
+
+        Self {
+            transitions,
+            outputs,
+            max_len,
+        }
+    }
+
+    #[inline]
+    fn next(&self, state: usize, c: u8) -> usize {
+        self.transitions[state][c as usize]
+    }
+}
+
+#[test]
+fn part1_example() {
+    assert_eq!(142, part1(include_str!("example1.txt")));
+}
+
+#[ignore]
+#[test]
+fn part1_verify() {
+    assert_eq!(55017, part1(INPUT));
+}
+
+#[test]
+fn part2_example() {
+    assert_eq!(281, part2(include_str!("example2.txt")));
+}
+
+#[ignore]
+#[test]
+fn part2_verify() {
+    assert_eq!(53539, part2(INPUT));
+}
+
+#[test]
+fn overlapping_words() {
+    let matcher = DigitMatcher::new(&Vocabulary::part2());
+    assert_eq!(Ok(82), parse_digits("eightwo", &matcher));
+    assert_eq!(Ok(18), parse_digits("oneight", &matcher));
+    assert_eq!(Ok(21), parse_digits("xtwonex", &matcher));
+    assert_eq!(Ok(77), parse_digits("sevenine7", &matcher));
+    assert_eq!(
+        Err("no digit in \"abc\"".to_string()),
+        parse_digits("abc", &matcher)
+    );
+    assert_eq!(
+        Some(Match {
+            start: 5,
+            end: 10,
+            value: 8
+        }),
+        matcher.find_last("nine8eight")
+    );
+}
+
+#[test]
+fn nested_words() {
+    let vocabulary = Vocabulary::parse("seven=7\nseventy=70\neventy=1\ny=2").unwrap();
+    let matcher = DigitMatcher::new(&vocabulary);
+    assert_eq!(Some(70), matcher.find_first("xseventy").map(|m| m.value));
+    assert_eq!(Some(2), matcher.find_last("xseventy").map(|m| m.value));
+    assert_eq!(Some(7), matcher.find_first("sevent").map(|m| m.value));
+}
+
+#[test]
+fn custom_vocabulary() {
+    let vocabulary = Vocabulary::parse(
+        "# German, with some tens
+eins=1
+zwei=2
+drei=3
+zehn=10
+zwanzig=20
+",
+    )
+    .and_then(|v| v.ignore_case(true))
+    .unwrap();
+    let input = "einsxdrei\nzwanzig\nzehnzwanzigeins\nZWEIxEins\n";
+    assert_eq!(
+        Ok(13 + 2020 + 101 + 21),
+        calibration_value(input, &vocabulary)
+    );
+
+    let vocabulary = Vocabulary::parse("big=2147483647\nsmall=1").unwrap();
+    assert_eq!(
+        Ok(21474836471 + 11),
+        calibration_value("bigxsmall\nsmall", &vocabulary)
+    );
+    assert_eq!(
+        Err("line 2: 2147483647 followed by 2147483647 overflows".to_string()),
+        calibration_value("small\nbigxbig", &vocabulary)
+    );
+}
+
+#[test]
+fn invalid_vocabulary() {
+    assert!(Vocabulary::parse("one=1\ntwo").is_err());
+    assert!(Vocabulary::parse("one=-1").is_err());
+    assert!(Vocabulary::parse("=1").is_err());
+    assert!(Vocabulary::parse("one=1\none=2").is_err());
+    let vocabulary = Vocabulary::parse("one=1\nONE=2").unwrap();
+    assert!(vocabulary.ignore_case(true).is_err());
+}
+
+#[test]
+fn explain_lines() {
+    let input = "xtwone3four\nnothing here\neightwo";
+    let explanations = explain(input, &Vocabulary::part2()).collect::<Vec<_>>();
+    assert_eq!(
+        vec![Ok(Some(24)), Ok(None), Ok(Some(82))],
+        explanations
+            .iter()
+            .map(Explanation::value)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        "    3:   82  first \"eight\"@0..5  last \"two\"@4..7  eightwo",
+        explanations[2].to_string()
+    );
+    let coloured = Explanation {
+        colour: true,
+        ..explanations.into_iter().nth(2).unwrap()
+    };
+    assert_eq!(
+        "    3:   82  first \"eight\"@0..5  last \"two\"@4..7  \
+         \x1b[1;32meigh\x1b[0m\x1b[1;33mt\x1b[0m\x1b[1;36mwo\x1b[0m",
+        coloured.to_string()
+    );
+
+    let mut out = Vec::new();
+    write_explanation(input, &Vocabulary::part2(), false, &mut out).unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.contains("    2:    -  no digit  nothing here"));
+    assert!(out.ends_with("total: 106\nlines without digits: 2\n"));
+    assert_eq!(
+        Err("line 2: no digit in \"nothing here\"".to_string()),
+        calibration_value(input, &Vocabulary::part2())
+    );
+}
@@ -0,0 +1,365 @@
+use std::fmt::{Display, Formatter};
+
+pub const INPUT: &str = include_str!("input.txt");
+
+pub const PUZZLE_BAG: &str = "12 red, 13 green, 14 blue";
+
+pub fn part1(input: &str) -> Result<i32, String> {
+    let bag = Bag::parse(PUZZLE_BAG).expect("puzzle bag");
+    possible_games(input, &bag)
+}
+
+pub fn part2(input: &str) -> Result<i32, String> {
+    let bag = Bag::parse(PUZZLE_BAG).expect("puzzle bag");
+    Ok(parse(input)?
+        .iter()
+        .map(|game| min_required_cubes(game).power(bag.colours()))
+        .sum::<i32>())
+}
+
+pub fn possible_games(input: &str, bag: &Bag) -> Result<i32, String> {
+    Ok(parse(input)?
+        .iter()
+        .filter(|game| is_possible(game, bag))
+        .map(|game| game.id)
+        .sum::<i32>())
+}
+
+// Counts by colour name, in the order they were listed. Used both for a single draw and for the
+// contents of a bag.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Cubes<'a>(Vec<(&'a str, i32)>);
+
+pub type Draw<'a> = Cubes<'a>;
+pub type Bag<'a> = Cubes<'a>;
+
+impl<'a> Cubes<'a> {
+    pub fn parse(s: &'a str) -> Result<Self, String> {
+        let mut cubes = Cubes::default();
+        for cube in s.trim().split(", ") {
+            let (n, colour) = cube
+                .split_once(' ')
+                .ok_or_else(|| format!("expected `<count> <colour>`, found {cube:?}"))?;
+            let n = n
+                .parse::<i32>()
+                .map_err(|_| format!("invalid count {n:?} of {colour}"))?;
+            if cubes.contains(colour) {
+                return Err(format!("{colour} is listed twice"));
+            }
+            cubes.0.push((colour, n));
+        }
+        Ok(cubes)
+    }
+
+    #[inline]
+    pub fn contains(&self, colour: &str) -> bool {
+        self.0.iter().any(|(c, _)| *c == colour)
+    }
+
+    #[inline]
+    pub fn get(&self, colour: &str) -> i32 {
+        self.0
+            .iter()
+            .find_map(|(c, n)| (*c == colour).then_some(*n))
+            .unwrap_or(0)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&'a str, i32)> + '_ {
+        self.0.iter().copied()
+    }
+
+    pub fn colours(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.0.iter().map(|(c, _)| *c)
+    }
+
+    // Raises the count of a colour to at least `n`, adding the colour if it is missing
+    fn raise(&mut self, colour: &'a str, n: i32) {
+        match self.0.iter_mut().find(|(c, _)| *c == colour) {
+            Some((_, m)) => *m = (*m).max(n),
+            None => self.0.push((colour, n)),
+        }
+    }
+
+    pub fn power<'b>(&self, colours: impl IntoIterator<Item = &'b str>) -> i32 {
+        colours.into_iter().map(|c| self.get(c)).product()
+    }
+}
+
+impl Display for Cubes<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (i, (colour, n)) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{n} {colour}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Game<'a> {
+    pub id: i32,
+    pub draws: Vec<Draw<'a>>,
+}
+
+impl Display for Game<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Game {}: ", self.id)?;
+        for (i, draw) in self.draws.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            draw.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+// Every line is a game, as skipping one would change the sums of both parts
+pub fn parse(input: &str) -> Result<Vec<Game<'_>>, String> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_game(line).map_err(|e| format!("line {}: {e}", i + 1)))
+        .collect()
+}
+
+pub fn parse_game(line: &str) -> Result<Game<'_>, String> {
+    let (id, draws) = line
+        .split_once(": ")
+        .and_then(|(game, draws)| Some((game.strip_prefix("Game ")?.parse::<i32>().ok()?, draws)))
+        .ok_or_else(|| format!("expected `Game <id>: <draws>`, found {line:?}"))?;
+    let draws = draws
+        .split("; ")
+        .map(Draw::parse)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Game { id, draws })
+}
+
+pub fn is_possible(game: &Game, bag: &Bag) -> bool {
+    game.draws
+        .iter()
+        .all(|draw| draw.iter().all(|(colour, n)| n <= bag.get(colour)))
+}
+
+pub fn min_required_cubes<'a>(game: &Game<'a>) -> Bag<'a> {
+    game.draws
+        .iter()
+        .flat_map(Draw::iter)
+        .fold(Bag::default(), |mut bag, (colour, n)| {
+            bag.raise(colour, n);
+            bag
+        })
+}
+
+pub fn minimal_bag<'a>(games: &[Game<'a>]) -> Bag<'a> {
+    games
+        .iter()
+        .map(min_required_cubes)
+        .fold(Bag::default(), |mut bag, required| {
+            for (colour, n) in required.iter() {
+                bag.raise(colour, n);
+            }
+            bag
+        })
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Conflict<'a> {
+    pub game: i32,
+    // How many more cubes of each colour the bag would need to hold
+    pub excess: Cubes<'a>,
+}
+
+pub fn conflicts<'a>(games: &[Game<'a>], bag: &Bag) -> Vec<Conflict<'a>> {
+    games
+        .iter()
+        .filter_map(|game| {
+            let excess = min_required_cubes(game)
+                .iter()
+                .map(|(colour, n)| (colour, n - bag.get(colour)))
+                .filter(|(_, n)| *n > 0)
+                .collect::<Vec<_>>();
+            (!excess.is_empty()).then_some(Conflict {
+                game: game.id,
+                excess: Cubes(excess),
+            })
+        })
+        .collect()
+}
+
+// Each draw is a handful taken from the bag without replacement, and put back before the next
+// draw. Colours of the bag that were not listed in a draw were drawn zero times.
+pub fn draw_log_likelihood(draw: &Draw, bag: &Bag) -> f64 {
+    if draw.colours().any(|colour| !bag.contains(colour)) {
+        return f64::NEG_INFINITY;
+    }
+    let drawn = draw.iter().map(|(_, n)| n).sum::<i32>();
+    let total = bag.iter().map(|(_, n)| n).sum::<i32>();
+    bag.iter()
+        .map(|(colour, n)| ln_choose(n, draw.get(colour)))
+        .sum::<f64>()
+        - ln_choose(total, drawn)
+}
+
+pub fn log_likelihood(games: &[Game], bag: &Bag) -> f64 {
+    games
+        .iter()
+        .flat_map(|game| &game.draws)
+        .map(|draw| draw_log_likelihood(draw, bag))
+        .sum()
+}
+
+// Most likely first. Bags that cannot produce every draw end up last, with a log likelihood of
+// negative infinity.
+pub fn rank_bags<'a>(games: &[Game], candidates: Vec<Bag<'a>>) -> Vec<(Bag<'a>, f64)> {
+    let mut ranked = candidates
+        .into_iter()
+        .map(|bag| {
+            let l = log_likelihood(games, &bag);
+            (bag, l)
+        })
+        .collect::<Vec<_>>();
+    ranked.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+    ranked
+}
+
+// Every bag holding `total` cubes of the colours in `minimum`, with at least as many of each
+// colour as `minimum` has
+pub fn bags_with_total<'a>(minimum: &Bag<'a>, total: i32) -> Vec<Bag<'a>> {
+    fn fill<'a>(
+        remaining: &[(&'a str, i32)],
+        total: i32,
+        bag: &mut Vec<(&'a str, i32)>,
+        bags: &mut Vec<Bag<'a>>,
+    ) {
+        match remaining {
+            [] if total == 0 => bags.push(Cubes(bag.clone())),
+            [] => {}
+            [(colour, min), rest @ ..] => {
+                let rest_min = rest.iter().map(|(_, n)| n).sum::<i32>();
+                for n in *min..=total - rest_min {
+                    bag.push((colour, n));
+                    fill(rest, total - n, bag, bags);
+                    bag.pop();
+                }
+            }
+        }
+    }
+
+    let mut bags = Vec::new();
+    fill(&minimum.0, total, &mut Vec::new(), &mut bags);
+    bags
+}
+
+fn ln_choose(n: i32, k: i32) -> f64 {
+    if k < 0 || k > n {
+        return f64::NEG_INFINITY;
+    }
+    let k = k.min(n - k);
+    (1..=k)
+        .map(|i| ((n - k + i) as f64).ln() - (i as f64).ln())
+        .sum()
+}
+
+#[test]
+fn part1_example() {
+    assert_eq!(Ok(8), part1(include_str!("example.txt")));
+}
+
+#[ignore]
+#[test]
+fn part1_verify() {
+    assert_eq!(Ok(2348), part1(INPUT));
+}
+
+#[test]
+fn part2_example() {
+    assert_eq!(Ok(2286), part2(include_str!("example.txt")));
+}
+
+#[ignore]
+#[test]
+fn part2_verify() {
+    assert_eq!(Ok(76008), part2(INPUT));
+}
+
+#[test]
+fn round_trip() {
+    for line in include_str!("example.txt").lines() {
+        assert_eq!(line, parse_game(line).unwrap().to_string());
+    }
+}
+
+#[test]
+fn other_colours() {
+    let input = "Game 1: 2 purple, 1 red; 3 orange\nGame 2: 4 purple\nGame 3: 1 teal";
+    let bag = Bag::parse("3 purple, 1 red, 5 orange").unwrap();
+    assert_eq!(Ok(1), possible_games(input, &bag));
+
+    let game = parse_game("Game 7: 2 purple, 1 red; 3 orange, 4 purple").unwrap();
+    let min = min_required_cubes(&game);
+    assert_eq!("4 purple, 1 red, 3 orange", min.to_string());
+    assert_eq!(12, min.power(min.colours()));
+    assert_eq!(0, min.power(["purple", "blue"]));
+}
+
+#[test]
+fn invalid_games() {
+    assert_eq!(
+        Err("red is listed twice".to_string()),
+        parse_game("Game 1: 2 red, 3 red")
+    );
+    assert_eq!(
+        Err("invalid count \"two\" of red".to_string()),
+        parse_game("Game 1: two red")
+    );
+    assert_eq!(
+        Err("expected `Game <id>: <draws>`, found \"Round 1: 2 red\"".to_string()),
+        parse_game("Round 1: 2 red")
+    );
+    assert_eq!(
+        Err("line 2: red is listed twice".to_string()),
+        part1("Game 1: 2 red\nGame 2: 2 red, 3 red")
+    );
+}
+
+#[test]
+fn infer_minimal_bag_and_conflicts() {
+    let games = parse(include_str!("example.txt")).unwrap();
+    assert_eq!("15 blue, 20 red, 13 green", minimal_bag(&games).to_string());
+
+    let bag = Bag::parse(PUZZLE_BAG).unwrap();
+    let conflicts = conflicts(&games, &bag);
+    assert_eq!(
+        vec![(3, "8 red".to_string()), (4, "2 red, 1 blue".to_string())],
+        conflicts
+            .iter()
+            .map(|c| (c.game, c.excess.to_string()))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn rank_candidate_bags() {
+    let games = vec![parse_game("Game 1: 2 red; 1 red, 1 blue").unwrap()];
+    let candidates = ["2 red, 1 blue", "3 red, 3 blue", "1 red, 5 blue", "2 red"]
+        .into_iter()
+        .map(|b| Bag::parse(b).unwrap())
+        .collect::<Vec<_>>();
+    let ranked = rank_bags(&games, candidates);
+
+    // 1/3 * 2/3 against 3/15 * 9/15
+    assert_eq!("2 red, 1 blue", ranked[0].0.to_string());
+    assert!((ranked[0].1 - (2.0f64 / 9.0).ln()).abs() < 1e-9);
+    assert_eq!("3 red, 3 blue", ranked[1].0.to_string());
+    assert!((ranked[1].1 - (27.0f64 / 225.0).ln()).abs() < 1e-9);
+    assert_eq!(f64::NEG_INFINITY, ranked[2].1);
+    assert_eq!(f64::NEG_INFINITY, ranked[3].1);
+
+    let minimum = minimal_bag(&games);
+    let bags = bags_with_total(&minimum, 5);
+    assert_eq!(3, bags.len());
+    assert_eq!("4 red, 1 blue", rank_bags(&games, bags)[0].0.to_string());
+}
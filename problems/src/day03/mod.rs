@@ -0,0 +1,377 @@
+#[cfg(test)]
+use adventofcode2023_common::random::XorShift;
+use adventofcode2023_common::StrExt;
+use std::collections::HashMap;
+use std::io::BufRead;
+
+pub const INPUT: &str = include_str!("input.txt");
+
+pub fn part1(input: &str) -> i32 {
+    let schematic = parse(input);
+    schematic.part_numbers().map(|n| n.value).sum::<i32>()
+}
+
+pub fn part2(input: &str) -> i32 {
+    let schematic = parse(input);
+    schematic
+        .symbols_with_neighbours('*', 2)
+        .map(|(_, numbers)| numbers.iter().map(|n| n.value).product::<i32>())
+        .sum()
+}
+
+pub fn parse(input: &str) -> Schematic<'_> {
+    Schematic::parse(input)
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Number<'a> {
+    pub xs: (i32, i32),
+    pub y: i32,
+    pub text: &'a str,
+    pub value: i32,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Symbol {
+    pub x: i32,
+    pub y: i32,
+    pub c: char,
+}
+
+// Numbers and symbols are related when they touch, diagonals included. The relation is kept in
+// both directions, indexed by position in `numbers` and `symbols`.
+pub struct Schematic<'a> {
+    pub numbers: Vec<Number<'a>>,
+    pub symbols: Vec<Symbol>,
+    symbols_by_number: Vec<Vec<usize>>,
+    numbers_by_symbol: Vec<Vec<usize>>,
+}
+
+impl<'a> Schematic<'a> {
+    pub fn parse(input: &'a str) -> Self {
+        let numbers = numbers_iter(input).collect::<Vec<_>>();
+        let symbols = symbols_iter(input).collect::<Vec<_>>();
+        let symbol_at = symbols
+            .iter()
+            .enumerate()
+            .map(|(i, s)| ((s.x, s.y), i))
+            .collect::<HashMap<_, _>>();
+
+        let mut numbers_by_symbol = vec![Vec::new(); symbols.len()];
+        let symbols_by_number = numbers
+            .iter()
+            .enumerate()
+            .map(|(i, n)| {
+                let adjacent_symbols = adjacent(n.xs.0, n.xs.1, n.y)
+                    .filter_map(|xy| symbol_at.get(&xy).copied())
+                    .collect::<Vec<_>>();
+                for s in &adjacent_symbols {
+                    numbers_by_symbol[*s].push(i);
+                }
+                adjacent_symbols
+            })
+            .collect();
+
+        Self {
+            numbers,
+            symbols,
+            symbols_by_number,
+            numbers_by_symbol,
+        }
+    }
+
+    pub fn symbols_adjacent_to(&self, number: usize) -> impl Iterator<Item = &Symbol> + '_ {
+        self.symbols_by_number[number]
+            .iter()
+            .map(|s| &self.symbols[*s])
+    }
+
+    pub fn numbers_adjacent_to(&self, symbol: usize) -> impl Iterator<Item = &Number<'a>> + '_ {
+        self.numbers_by_symbol[symbol]
+            .iter()
+            .map(|n| &self.numbers[*n])
+    }
+
+    // Numbers touching at least one symbol
+    pub fn part_numbers(&self) -> impl Iterator<Item = &Number<'a>> + '_ {
+        self.numbers
+            .iter()
+            .zip(&self.symbols_by_number)
+            .filter_map(|(n, symbols)| (!symbols.is_empty()).then_some(n))
+    }
+
+    // Symbols `c` touching exactly `n` numbers, with those numbers
+    pub fn symbols_with_neighbours(
+        &self,
+        c: char,
+        n: usize,
+    ) -> impl Iterator<Item = (&Symbol, Vec<&Number<'a>>)> + '_ {
+        self.symbols
+            .iter()
+            .enumerate()
+            .filter(move |(i, s)| s.c == c && self.numbers_by_symbol[*i].len() == n)
+            .map(|(i, s)| (s, self.numbers_adjacent_to(i).collect()))
+    }
+
+    pub fn lonely_symbols(&self) -> impl Iterator<Item = &Symbol> + '_ {
+        self.symbols
+            .iter()
+            .zip(&self.numbers_by_symbol)
+            .filter_map(|(s, numbers)| numbers.is_empty().then_some(s))
+    }
+}
+
+fn symbols_iter(input: &str) -> impl Iterator<Item = Symbol> + '_ {
+    input.lines().enumerate().flat_map(move |(y, line)| {
+        find(line, next_symbol).map(move |((x, _), s)| Symbol {
+            x,
+            y: y as i32,
+            c: s.chars().next().unwrap(),
+        })
+    })
+}
+
+fn numbers_iter(input: &str) -> impl Iterator<Item = Number<'_>> + '_ {
+    input.lines().enumerate().flat_map(|(y, line)| {
+        find(line, next_number).map(move |(xs, text)| Number {
+            xs,
+            y: y as i32,
+            text,
+            value: text.to_i32(),
+        })
+    })
+}
+
+fn adjacent(x1: i32, x2: i32, y: i32) -> impl Iterator<Item = (i32, i32)> {
+    let above = (x1 - 1..x2 + 1).map(move |x| (x, y - 1));
+    let below = (x1 - 1..x2 + 1).map(move |x| (x, y + 1));
+    above.chain(below).chain([(x1 - 1, y), (x2, y)])
+}
+
+fn find(
+    line: &str,
+    searcher: fn(&str) -> Option<(usize, usize)>,
+) -> impl Iterator<Item = ((i32, i32), &str)> + '_ {
+    let mut pos = 0;
+    std::iter::from_fn(move || {
+        let (start, end) = searcher(&line[pos..]).map(|(s, e)| (s + pos, e + pos))?;
+        pos = end;
+        Some(((start as i32, end as i32), &line[start..end]))
+    })
+}
+
+fn next_number(line: &str) -> Option<(usize, usize)> {
+    let start = line.find(|c: char| c.is_ascii_digit())?;
+    let end = line[start..]
+        .find(|c: char| !c.is_ascii_digit())
+        .map(|pos| pos + start)
+        .unwrap_or(line.len());
+    Some((start, end))
+}
+
+fn next_symbol(line: &str) -> Option<(usize, usize)> {
+    let start = line.find(|c: char| c != '.' && !c.is_ascii_digit())?;
+    let len = line[start..].chars().next()?.len_utf8();
+    Some((start, start + len))
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct Totals {
+    pub part_numbers: i64,
+    pub gear_ratios: i64,
+}
+
+// Reads the schematic one row at a time, keeping only the rows above and below the one being
+// scanned, so both parts are answered in a single pass whatever the size of the input.
+pub fn scan(mut reader: impl BufRead) -> std::io::Result<Totals> {
+    let mut totals = Totals::default();
+    // above, current, below
+    let mut window: [Vec<u8>; 3] = Default::default();
+    let mut more = read_row(&mut reader, &mut window[2])?;
+    while more {
+        window.rotate_left(1);
+        more = read_row(&mut reader, &mut window[2])?;
+        scan_row(&window, &mut totals);
+    }
+    Ok(totals)
+}
+
+fn read_row(reader: &mut impl BufRead, row: &mut Vec<u8>) -> std::io::Result<bool> {
+    row.clear();
+    if reader.read_until(b'\n', row)? == 0 {
+        return Ok(false);
+    }
+    if row.last() == Some(&b'\n') {
+        row.pop();
+        if row.last() == Some(&b'\r') {
+            row.pop();
+        }
+    }
+    Ok(true)
+}
+
+fn scan_row(window: &[Vec<u8>; 3], totals: &mut Totals) {
+    let row = &window[1];
+
+    let mut x = 0;
+    while x < row.len() {
+        if !row[x].is_ascii_digit() {
+            x += 1;
+            continue;
+        }
+        let (start, end, value) = number_at(row, x).unwrap();
+        if window.iter().any(|r| {
+            r.iter()
+                .take(end + 1)
+                .skip(start.saturating_sub(1))
+                .any(|b| is_symbol(*b))
+        }) {
+            totals.part_numbers += value;
+        }
+        x = end;
+    }
+
+    for (x, _) in row.iter().enumerate().filter(|(_, b)| **b == b'*') {
+        let mut count = 0;
+        let mut ratio = 1;
+        for r in window {
+            // A digit straight above or below the gear belongs to the only number on that row
+            // that can touch it; otherwise there may be one either side.
+            let candidates = if r.get(x).is_some_and(u8::is_ascii_digit) {
+                [number_at(r, x), None]
+            } else {
+                [
+                    x.checked_sub(1).and_then(|x| number_at(r, x)),
+                    number_at(r, x + 1),
+                ]
+            };
+            for (_, _, value) in candidates.into_iter().flatten() {
+                count += 1;
+                ratio *= value;
+            }
+        }
+        if count == 2 {
+            totals.gear_ratios += ratio;
+        }
+    }
+}
+
+// The extent and value of the number covering `x`, if any
+fn number_at(row: &[u8], x: usize) -> Option<(usize, usize, i64)> {
+    if !row.get(x)?.is_ascii_digit() {
+        return None;
+    }
+    let start = row[..x]
+        .iter()
+        .rposition(|b| !b.is_ascii_digit())
+        .map_or(0, |i| i + 1);
+    let end = row[x..]
+        .iter()
+        .position(|b| !b.is_ascii_digit())
+        .map_or(row.len(), |i| i + x);
+    let value = row[start..end]
+        .iter()
+        .fold(0, |value, b| value * 10 + i64::from(b - b'0'));
+    Some((start, end, value))
+}
+
+// Matches `next_symbol`, which reports a multi-byte character once, at its first byte
+#[inline]
+fn is_symbol(b: u8) -> bool {
+    b != b'.' && !b.is_ascii_digit() && !(0x80..0xc0).contains(&b)
+}
+
+#[test]
+fn part1_example() {
+    assert_eq!(4361, part1(include_str!("example.txt")));
+}
+
+#[ignore]
+#[test]
+fn part1_verify() {
+    assert_eq!(514969, part1(INPUT));
+}
+
+#[test]
+fn part2_example() {
+    assert_eq!(467835, part2(include_str!("example.txt")));
+}
+
+#[ignore]
+#[test]
+fn part2_verify() {
+    assert_eq!(78915902, part2(INPUT));
+}
+
+#[test]
+fn schematic_queries() {
+    let schematic = parse(include_str!("example.txt"));
+    assert_eq!(10, schematic.numbers.len());
+    assert_eq!(6, schematic.symbols.len());
+    assert_eq!(
+        vec![114, 58],
+        schematic
+            .numbers
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| schematic.symbols_adjacent_to(*i).next().is_none())
+            .map(|(_, n)| n.value)
+            .collect::<Vec<_>>()
+    );
+
+    let lonely = "1.*\n..#\n.$2";
+    let schematic = parse(lonely);
+    assert_eq!(
+        vec!['*'],
+        schematic.lonely_symbols().map(|s| s.c).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![(Symbol { x: 1, y: 2, c: '$' }, vec![2])],
+        schematic
+            .symbols_with_neighbours('$', 1)
+            .map(|(s, numbers)| (*s, numbers.iter().map(|n| n.value).collect::<Vec<_>>()))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn scan_matches_parts() {
+    fn check(input: &str) {
+        let expected = Totals {
+            part_numbers: part1(input) as i64,
+            gear_ratios: part2(input) as i64,
+        };
+        assert_eq!(expected, scan(input.as_bytes()).unwrap());
+        let reader = std::io::BufReader::with_capacity(3, input.as_bytes());
+        assert_eq!(expected, scan(reader).unwrap());
+    }
+
+    check(include_str!("example.txt"));
+    check(&include_str!("example.txt").replace('\n', "\r\n"));
+    check("12*\n*3\n4\n");
+    check("");
+
+    let mut random = XorShift::new(0x9e37_79b9_7f4a_7c15);
+    for _ in 0..50 {
+        let (width, height) = (1 + random.below(40), 1 + random.below(40));
+        let mut input = String::new();
+        for _ in 0..height {
+            // Numbers are kept to three digits, as in the puzzle, so that `part2` cannot overflow
+            let mut digits = 0;
+            for _ in 0..width {
+                let c = match random.below(16) {
+                    _ if digits == 3 => '.',
+                    0..=5 => '.',
+                    6 | 7 => '*',
+                    8 => '#',
+                    9 => '€',
+                    _ => char::from(b'0' + random.below(10) as u8),
+                };
+                digits = if c.is_ascii_digit() { digits + 1 } else { 0 };
+                input.push(c);
+            }
+            input.push('\n');
+        }
+        check(&input);
+    }
+}
@@ -0,0 +1,327 @@
+use std::fmt::{Display, Formatter};
+use std::io::{self, Write};
+
+pub const INPUT: &str = include_str!("input.txt");
+
+pub fn part1(input: &str) -> u64 {
+    let cards = parse(input).unwrap_or_else(|e| panic!("{e}"));
+    total_points(&cards, &Rules::PUZZLE).unwrap_or_else(|e| panic!("{e}"))
+}
+
+pub fn part2(input: &str) -> u64 {
+    let cards = parse(input).unwrap_or_else(|e| panic!("{e}"));
+    total_cards(&cards, &Rules::PUZZLE).unwrap_or_else(|e| panic!("{e}"))
+}
+
+// Every line is a card, as skipping one would shift the copies won by all the cards before it
+pub fn parse(input: &str) -> Result<Vec<Card>, String> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_card(line).map_err(|e| format!("line {}: {e}", i + 1)))
+        .collect()
+}
+
+// Card numbers are below 100 in the puzzle, so the winning numbers fit in a single `u128`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Card {
+    pub winning: u128,
+    pub numbers: Vec<u8>,
+}
+
+impl Card {
+    pub fn matches(&self) -> usize {
+        self.numbers
+            .iter()
+            .filter(|n| self.winning & (1 << *n) != 0)
+            .count()
+    }
+}
+
+pub fn parse_card(line: &str) -> Result<Card, String> {
+    let (winning_numbers, numbers) = line
+        .split_once(": ")
+        .and_then(|(_card, numbers)| numbers.split_once(" | "))
+        .ok_or_else(|| format!("expected `Card <n>: <winning> | <numbers>`, got {line:?}"))?;
+    let winning = winning_numbers
+        .split_whitespace()
+        .try_fold(0u128, |winning, n| {
+            parse_number(n).map(|n| winning | 1 << n)
+        })?;
+    let numbers = numbers
+        .split_whitespace()
+        .map(parse_number)
+        .collect::<Result<_, String>>()?;
+    Ok(Card { winning, numbers })
+}
+
+fn parse_number(n: &str) -> Result<u8, String> {
+    n.parse()
+        .ok()
+        .filter(|n| u32::from(*n) < u128::BITS)
+        .ok_or_else(|| format!("invalid number {n:?}, expected 0 to {}", u128::BITS - 1))
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Scoring {
+    // One point per match
+    Linear,
+    // One point for the first match, doubled for each further match
+    Exponential,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Window {
+    // As many following cards as there are matches
+    Matches,
+    // This many following cards for any card with at least one match
+    Fixed(usize),
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Edge {
+    // Copies past the end of the table are not won
+    Truncate,
+    // Copies continue from the start of the table. The table is still scratched once, in order, so
+    // copies won of cards already scratched are held but win nothing themselves.
+    Wrap,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Rules {
+    pub scoring: Scoring,
+    pub window: Window,
+    pub edge: Edge,
+}
+
+impl Rules {
+    pub const PUZZLE: Self = Self {
+        scoring: Scoring::Exponential,
+        window: Window::Matches,
+        edge: Edge::Truncate,
+    };
+
+    pub fn points(&self, matches: usize) -> Option<u64> {
+        match (self.scoring, matches) {
+            (_, 0) => Some(0),
+            (Scoring::Linear, m) => u64::try_from(m).ok(),
+            (Scoring::Exponential, m) => 1u64.checked_shl(u32::try_from(m - 1).ok()?),
+        }
+    }
+
+    // Indices of the cards that card `i` of `len` wins copies of
+    pub fn won(&self, i: usize, matches: usize, len: usize) -> impl Iterator<Item = usize> {
+        let size = match self.window {
+            Window::Matches => matches,
+            Window::Fixed(_) if matches == 0 => 0,
+            Window::Fixed(n) => n,
+        };
+        let edge = self.edge;
+        (i + 1..).take(size).map_while(move |j| match edge {
+            Edge::Truncate => (j < len).then_some(j),
+            Edge::Wrap => Some(j % len),
+        })
+    }
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Self::PUZZLE
+    }
+}
+
+// The copies held of each card, counting the original. Cards only scratch the copies they hold
+// when they are reached, so every count that matters is final by then.
+pub fn copies(cards: &[Card], rules: &Rules) -> Result<Vec<u64>, String> {
+    let mut copies = vec![1u64; cards.len()];
+    for (i, card) in cards.iter().enumerate() {
+        let held = copies[i];
+        for j in rules.won(i, card.matches(), cards.len()) {
+            copies[j] = copies[j]
+                .checked_add(held)
+                .ok_or_else(|| format!("copies of card {} overflow u64", j + 1))?;
+        }
+    }
+    Ok(copies)
+}
+
+pub fn total_cards(cards: &[Card], rules: &Rules) -> Result<u64, String> {
+    copies(cards, rules)?
+        .into_iter()
+        .try_fold(0u64, |total, n| total.checked_add(n))
+        .ok_or_else(|| "total number of cards overflows u64".to_string())
+}
+
+// Points are scored by the original cards only
+pub fn total_points(cards: &[Card], rules: &Rules) -> Result<u64, String> {
+    cards.iter().enumerate().try_fold(0u64, |total, (i, card)| {
+        rules
+            .points(card.matches())
+            .and_then(|points| total.checked_add(points))
+            .ok_or_else(|| format!("points of card {} overflow u64", i + 1))
+    })
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Trace {
+    pub card: usize,
+    pub matches: usize,
+    pub copies: u64,
+    pub points: u64,
+}
+
+impl Display for Trace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "card {:>4}: {:>3} matches  {:>10} copies  {:>6} points",
+            self.card, self.matches, self.copies, self.points
+        )
+    }
+}
+
+pub fn trace(cards: &[Card], rules: &Rules) -> Result<Vec<Trace>, String> {
+    let copies = copies(cards, rules)?;
+    cards
+        .iter()
+        .zip(copies)
+        .enumerate()
+        .map(|(i, (card, copies))| {
+            let matches = card.matches();
+            let points = rules
+                .points(matches)
+                .ok_or_else(|| format!("points of card {} overflow u64", i + 1))?;
+            Ok(Trace {
+                card: i + 1,
+                matches,
+                copies,
+                points,
+            })
+        })
+        .collect()
+}
+
+pub fn write_trace(cards: &[Card], rules: &Rules, w: &mut impl Write) -> io::Result<()> {
+    for trace in trace(cards, rules).map_err(io::Error::other)? {
+        writeln!(w, "{trace}")?;
+    }
+    let points = total_points(cards, rules).map_err(io::Error::other)?;
+    let total = total_cards(cards, rules).map_err(io::Error::other)?;
+    writeln!(w, "points: {points}  cards: {total}")
+}
+
+#[test]
+fn part1_example() {
+    assert_eq!(13, part1(include_str!("example.txt")));
+}
+
+#[ignore]
+#[test]
+fn part1_verify() {
+    assert_eq!(21919, part1(INPUT));
+}
+
+#[test]
+fn part2_example() {
+    assert_eq!(30, part2(include_str!("example.txt")));
+}
+
+#[ignore]
+#[test]
+fn part2_verify() {
+    assert_eq!(9881048, part2(INPUT));
+}
+
+#[test]
+fn copies_example() {
+    let cards = parse(include_str!("example.txt")).unwrap();
+    assert_eq!(
+        vec![4, 2, 2, 1, 0, 0],
+        cards.iter().map(Card::matches).collect::<Vec<_>>()
+    );
+    assert_eq!(Ok(vec![1, 2, 4, 8, 14, 1]), copies(&cards, &Rules::PUZZLE));
+    assert_eq!(
+        Err("line 2: invalid number \"128\", expected 0 to 127".to_string()),
+        parse("Card 1: 1 | 1\nCard 2: 128 | 1")
+    );
+    assert_eq!(
+        Err("line 1: expected `Card <n>: <winning> | <numbers>`, got \"Card 1: 1\"".to_string()),
+        parse("Card 1: 1")
+    );
+}
+
+#[test]
+fn long_chains() {
+    // Each card wins one copy of the next, which used to recurse once per card
+    let one = parse_card("Card 1: 1 | 1").unwrap();
+    let cards = vec![one; 100_000];
+    assert_eq!(
+        Ok(100_000 * 100_001 / 2),
+        total_cards(&cards, &Rules::PUZZLE)
+    );
+
+    // Winning two cards each makes the copies grow like the Fibonacci numbers
+    let two = parse_card("Card 1: 1 2 | 1 2").unwrap();
+    let cards = vec![two; 100];
+    assert_eq!(
+        Err("copies of card 92 overflow u64".to_string()),
+        total_cards(&cards, &Rules::PUZZLE)
+    );
+}
+
+#[test]
+fn rules() {
+    let cards = parse(include_str!("example.txt")).unwrap();
+    let linear = Rules {
+        scoring: Scoring::Linear,
+        ..Rules::PUZZLE
+    };
+    assert_eq!(Ok(9), total_points(&cards, &linear));
+
+    let wrap = Rules {
+        edge: Edge::Wrap,
+        ..Rules::PUZZLE
+    };
+    let fixed = Rules {
+        window: Window::Fixed(1),
+        ..Rules::PUZZLE
+    };
+    assert_eq!(Ok(vec![1, 2, 4, 8, 14, 1]), copies(&cards, &wrap));
+    assert_eq!(Ok(vec![1, 2, 3, 4, 5, 1]), copies(&cards, &fixed));
+
+    // The third card's four matches reach past the end of the table, twice round to the first
+    // card and once round to itself
+    let cards = [
+        "Card 1: 1 2 | 1 3",
+        "Card 2: 1 | 2",
+        "Card 3: 1 2 3 4 | 1 2 3 4",
+    ]
+    .join("\n");
+    let cards = parse(&cards).unwrap();
+    assert_eq!(Ok(vec![1, 2, 1]), copies(&cards, &Rules::PUZZLE));
+    assert_eq!(Ok(vec![3, 3, 2]), copies(&cards, &wrap));
+
+    assert_eq!(
+        Ok(vec![
+            Trace {
+                card: 1,
+                matches: 1,
+                copies: 3,
+                points: 1
+            },
+            Trace {
+                card: 2,
+                matches: 0,
+                copies: 3,
+                points: 0
+            },
+            Trace {
+                card: 3,
+                matches: 4,
+                copies: 2,
+                points: 8
+            },
+        ]),
+        trace(&cards, &wrap)
+    );
+}
@@ -0,0 +1,839 @@
+use std::cmp::{max, min};
+use std::collections::HashMap;
+use std::fmt::{Debug, Display, Formatter};
+
+pub const INPUT: &str = include_str!("input.txt");
+
+pub fn part1(input: &str) -> i64 {
+    let almanac = parse(input).unwrap_or_else(|e| panic!("{e}"));
+    let map = almanac
+        .map("seed", "location")
+        .unwrap_or_else(|e| panic!("{e}"));
+
+    find_min_location(seeds_as_values(&almanac.seeds), &map)
+}
+
+pub fn part2(input: &str) -> i64 {
+    let almanac = parse(input).unwrap_or_else(|e| panic!("{e}"));
+    let map = almanac
+        .map("seed", "location")
+        .unwrap_or_else(|e| panic!("{e}"));
+    let seeds = seeds_as_ranges(&almanac.seeds).unwrap_or_else(|e| panic!("{e}"));
+
+    find_min_location(seeds, &map)
+}
+
+pub fn find_min_location(values: Vec<Range>, map: &Piecewise) -> i64 {
+    values
+        .into_iter()
+        .flat_map(|v| map.map_range(v))
+        .map(|r| r.start)
+        .min()
+        .expect("at least one value")
+}
+
+pub fn parse(input: &str) -> Result<Almanac<'_>, String> {
+    let (seeds, tables) = input
+        .split_once("\n\n")
+        .ok_or("expected seeds and maps separated by a blank line")?;
+    let seeds = parse_seeds(seeds)?;
+    let tables = tables
+        .split("\n\n")
+        .map(str::trim)
+        .filter(|table| !table.is_empty())
+        .map(parse_table)
+        .collect::<Result<Vec<_>, _>>()?;
+    Almanac::new(seeds, tables)
+}
+
+// Rejects almanacs with any table that does not say unambiguously where each value goes
+pub fn parse_strict(input: &str) -> Result<Almanac<'_>, String> {
+    let almanac = parse(input)?;
+    let ambiguous = almanac
+        .tables
+        .iter()
+        .flat_map(|table| {
+            table
+                .validate()
+                .into_iter()
+                .filter(Issue::is_ambiguous)
+                .map(move |issue| format!("{}: {issue}", table.name()))
+        })
+        .collect::<Vec<_>>();
+    if ambiguous.is_empty() {
+        Ok(almanac)
+    } else {
+        Err(ambiguous.join("\n"))
+    }
+}
+
+fn parse_seeds(seeds: &str) -> Result<Vec<i64>, String> {
+    let seeds = seeds
+        .strip_prefix("seeds: ")
+        .ok_or_else(|| format!("expected `seeds: ...`, found `{seeds}`"))?;
+    seeds
+        .split_ascii_whitespace()
+        .map(|s| s.parse::<i64>().map_err(|e| format!("seed `{s}`: {e}")))
+        .collect()
+}
+
+pub fn seeds_as_values(seeds: &[i64]) -> Vec<Range> {
+    seeds.iter().copied().map(Range::value).collect()
+}
+
+pub fn seeds_as_ranges(seeds: &[i64]) -> Result<Vec<Range>, String> {
+    seeds
+        .chunks(2)
+        .map(|chunk| match *chunk {
+            [start, length] if length < 0 => Err(format!(
+                "seed range starting at {start} has negative length {length}"
+            )),
+            [start, length] => start
+                .checked_add(length)
+                .map(|end| Range::new(start, end))
+                .ok_or_else(|| format!("seed range {start} + {length} overflows i64")),
+            _ => Err("seeds do not pair up into ranges".to_string()),
+        })
+        .collect()
+}
+
+fn parse_table(table: &str) -> Result<Table<'_>, String> {
+    let (header, entries) = table.split_once('\n').unwrap_or((table, ""));
+    let (source, destination) = header
+        .strip_suffix(" map:")
+        .and_then(|categories| categories.split_once("-to-"))
+        .ok_or_else(|| format!("expected `<source>-to-<destination> map:`, found `{header}`"))?;
+    let mut issues = Vec::new();
+    let mut parsed = Vec::new();
+    for (i, line) in entries.lines().enumerate() {
+        let numbers = line
+            .split_ascii_whitespace()
+            .map(|n| n.parse::<i64>().ok())
+            .collect::<Option<Vec<_>>>();
+        let Some(&[destination, source, length]) = numbers.as_deref() else {
+            return Err(format!(
+                "{header} expected `<destination> <source> <length>`, found `{line}`"
+            ));
+        };
+        let entry = i + 1;
+        if length < 0 {
+            return Err(format!(
+                "{header} entry {entry} has negative length {length}"
+            ));
+        }
+        let end = source.checked_add(length);
+        let offset = destination.checked_sub(source);
+        match (end, offset, destination.checked_add(length)) {
+            _ if length == 0 => issues.push(Issue::Empty { entry }),
+            (Some(end), Some(offset), Some(_)) => parsed.push(Entry {
+                range: Range::new(source, end),
+                offset,
+            }),
+            _ => issues.push(Issue::Overflow { entry }),
+        }
+    }
+    parsed.sort_by_key(Entry::start);
+    Ok(Table {
+        source,
+        destination,
+        entries: parsed,
+        issues,
+    })
+}
+
+// Each category is converted by at most one map, so the conversion path from any category is
+// found by following maps by name, in whatever order they appear.
+pub struct Almanac<'a> {
+    pub seeds: Vec<i64>,
+    pub tables: Vec<Table<'a>>,
+    by_source: HashMap<&'a str, usize>,
+}
+
+impl<'a> Almanac<'a> {
+    pub fn new(seeds: Vec<i64>, tables: Vec<Table<'a>>) -> Result<Self, String> {
+        let mut by_source = HashMap::new();
+        for (i, table) in tables.iter().enumerate() {
+            if let Some(j) = by_source.insert(table.source, i) {
+                return Err(format!(
+                    "`{}` is converted by two maps, to `{}` and to `{}`",
+                    table.source, tables[j].destination, table.destination
+                ));
+            }
+        }
+
+        let almanac = Self {
+            seeds,
+            tables,
+            by_source,
+        };
+        for table in &almanac.tables {
+            let mut path = vec![table.source];
+            let mut category = table.source;
+            while let Some(next) = almanac.next(category) {
+                category = next.destination;
+                path.push(category);
+                if category == table.source {
+                    return Err(format!("maps form a cycle: {}", path.join(" -> ")));
+                }
+                if path.len() > almanac.tables.len() + 1 {
+                    break;
+                }
+            }
+        }
+        Ok(almanac)
+    }
+
+    fn next(&self, source: &str) -> Option<&Table<'a>> {
+        self.by_source.get(source).map(|i| &self.tables[*i])
+    }
+
+    pub fn categories(&self) -> impl Iterator<Item = &'a str> + '_ {
+        let mut seen = Vec::new();
+        self.tables
+            .iter()
+            .flat_map(|t| [t.source, t.destination])
+            .filter(move |c| {
+                let new = !seen.contains(c);
+                if new {
+                    seen.push(*c);
+                }
+                new
+            })
+    }
+
+    // The maps converting `from` into `to`, in the order they apply
+    pub fn chain(&self, from: &str, to: &str) -> Result<Vec<&Table<'a>>, String> {
+        let mut chain = Vec::new();
+        let mut category = from;
+        while category != to {
+            let Some(table) = self.next(category) else {
+                return Err(if category == from {
+                    format!("no map converts `{from}`")
+                } else {
+                    format!(
+                        "no conversion from `{from}` to `{to}`: the chain stops at `{category}`"
+                    )
+                });
+            };
+            chain.push(table);
+            category = table.destination;
+        }
+        Ok(chain)
+    }
+
+    pub fn map(&self, from: &str, to: &str) -> Result<Piecewise, String> {
+        Ok(Piecewise::compose(self.chain(from, to)?))
+    }
+
+    // The values of category `to` for the values `ranges` of category `from`
+    pub fn convert(&self, from: &str, to: &str, ranges: &[Range]) -> Result<Vec<Range>, String> {
+        let map = self.map(from, to)?;
+        let mut converted = ranges
+            .iter()
+            .flat_map(|r| map.map_range(*r))
+            .collect::<Vec<_>>();
+        converted.sort_unstable_by_key(|r| r.start);
+        Ok(converted)
+    }
+}
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct Range {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Range {
+    #[inline]
+    pub const fn value(start: i64) -> Self {
+        Self {
+            start,
+            end: start + 1,
+        }
+    }
+
+    #[inline]
+    pub const fn new(start: i64, end: i64) -> Self {
+        Self { start, end }
+    }
+
+    #[inline]
+    fn offset(self, offset: i64) -> Self {
+        Self {
+            start: self.start + offset,
+            end: self.end + offset,
+        }
+    }
+
+    #[inline]
+    const fn intersects(&self, b: &Range) -> bool {
+        self.end > b.start && b.end > self.start
+    }
+
+    #[inline]
+    const fn preceeds(&self, b: &Range) -> bool {
+        self.start < b.start
+    }
+
+    #[inline]
+    const fn succeeds(&self, b: &Range) -> bool {
+        self.end > b.end
+    }
+
+    fn intersect(&self, b: &Range) -> (Option<Range>, Option<Range>, Option<Range>) {
+        let before = self
+            .preceeds(b)
+            .then(|| Range::new(self.start, min(self.end, b.start)));
+        let overlap = self
+            .intersects(b)
+            .then(|| Range::new(max(self.start, b.start), min(self.end, b.end)));
+        let after = self.succeeds(b).then(|| Range::new(b.end, self.end));
+
+        (before, overlap, after)
+    }
+}
+
+impl Debug for Range {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Entry {
+    range: Range,
+    offset: i64,
+}
+
+impl Debug for Entry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?} -> {}", self.range, self.offset)
+    }
+}
+
+impl Entry {
+    #[inline]
+    const fn start(&self) -> i64 {
+        self.range.start
+    }
+}
+
+pub struct Table<'a> {
+    pub source: &'a str,
+    pub destination: &'a str,
+    entries: Vec<Entry>,
+    // Entries left out of `entries` when parsing
+    issues: Vec<Issue>,
+}
+
+impl Table<'_> {
+    pub fn name(&self) -> String {
+        format!("{}-to-{} map", self.source, self.destination)
+    }
+
+    pub fn validate(&self) -> Vec<Issue> {
+        let mut issues = self.issues.clone();
+        for (i, first) in self.entries.iter().enumerate() {
+            for second in self.entries[i + 1..]
+                .iter()
+                .take_while(|e| e.range.start < first.range.end)
+            {
+                issues.push(Issue::Overlap {
+                    first: first.range,
+                    second: second.range,
+                    conflicting: first.offset != second.offset,
+                });
+            }
+        }
+        issues
+    }
+
+    // Values no entry covers, which pass through unchanged, including those before the first entry
+    // and after the last. `i64::MAX` itself is never covered, as ranges end before it.
+    pub fn gaps(&self) -> Vec<Range> {
+        let mut gaps = Vec::new();
+        let mut end = i64::MIN;
+        for entry in &self.entries {
+            if entry.range.start > end {
+                gaps.push(Range::new(end, entry.range.start));
+            }
+            end = max(end, entry.range.end);
+        }
+        if end < i64::MAX {
+            gaps.push(Range::new(end, i64::MAX));
+        }
+        gaps
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Issue {
+    // Source ranges of two entries share values. Where the entries also disagree on where those
+    // values go, the result depends on which entry is applied.
+    Overlap {
+        first: Range,
+        second: Range,
+        conflicting: bool,
+    },
+    // An entry of length zero, which maps nothing. Entries are counted from 1 within their table.
+    Empty {
+        entry: usize,
+    },
+    // An entry whose source or destination range does not fit in `i64`, which is left out
+    Overflow {
+        entry: usize,
+    },
+}
+
+impl Issue {
+    // Entries that are not in the map at all
+    pub fn is_skipped(&self) -> bool {
+        matches!(self, Issue::Empty { .. } | Issue::Overflow { .. })
+    }
+
+    pub fn is_ambiguous(&self) -> bool {
+        match self {
+            Issue::Overlap { conflicting, .. } => *conflicting,
+            Issue::Empty { .. } => false,
+            Issue::Overflow { .. } => true,
+        }
+    }
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Issue::Overlap {
+                first,
+                second,
+                conflicting,
+            } => {
+                let how = if *conflicting {
+                    "conflicting"
+                } else {
+                    "matching"
+                };
+                write!(
+                    f,
+                    "entries for {first:?} and {second:?} overlap with {how} destinations"
+                )
+            }
+            Issue::Empty { entry } => write!(f, "entry {entry} has length zero and is skipped"),
+            Issue::Overflow { entry } => write!(f, "entry {entry} overflows i64 and is skipped"),
+        }
+    }
+}
+
+// A map that adds a constant offset to every value in each of a sorted run of segments. The
+// segments cover all of `i64`: each starts at its entry in `starts` and ends where the next one
+// starts, the last one ending at `i64::MAX`. Neighbouring segments always have different offsets.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Piecewise {
+    starts: Vec<i64>,
+    offsets: Vec<i64>,
+}
+
+impl Piecewise {
+    pub fn identity() -> Self {
+        Self {
+            starts: vec![i64::MIN],
+            offsets: vec![0],
+        }
+    }
+
+    // Values outside every entry pass through unchanged. Where entries overlap the earlier one
+    // wins.
+    pub fn from_table(table: &Table) -> Self {
+        let mut map = Self {
+            starts: Vec::new(),
+            offsets: Vec::new(),
+        };
+        let mut cursor = i64::MIN;
+        for entry in &table.entries {
+            let start = max(entry.range.start, cursor);
+            if start >= entry.range.end {
+                continue;
+            }
+            if start > cursor {
+                map.push(cursor, 0);
+            }
+            map.push(start, entry.offset);
+            cursor = entry.range.end;
+        }
+        if map.starts.is_empty() || cursor < i64::MAX {
+            map.push(cursor, 0);
+        }
+        map
+    }
+
+    // The tables applied one after another, as a single map
+    pub fn compose<'t>(tables: impl IntoIterator<Item = &'t Table<'t>>) -> Self {
+        tables.into_iter().fold(Self::identity(), |map, table| {
+            map.then(&Self::from_table(table))
+        })
+    }
+
+    pub fn then(&self, next: &Piecewise) -> Self {
+        let mut map = Self {
+            starts: Vec::new(),
+            offsets: Vec::new(),
+        };
+        for (range, offset) in self.segments() {
+            for (piece, next_offset) in next.split(range.offset(offset)) {
+                map.push(piece.start - offset, offset + next_offset);
+            }
+        }
+        map
+    }
+
+    fn push(&mut self, start: i64, offset: i64) {
+        if self.offsets.last() != Some(&offset) {
+            self.starts.push(start);
+            self.offsets.push(offset);
+        }
+    }
+
+    pub fn segments(&self) -> impl Iterator<Item = (Range, i64)> + '_ {
+        (0..self.starts.len()).map(|i| self.segment(i))
+    }
+
+    fn segment(&self, i: usize) -> (Range, i64) {
+        let end = self.starts.get(i + 1).copied().unwrap_or(i64::MAX);
+        (Range::new(self.starts[i], end), self.offsets[i])
+    }
+
+    #[inline]
+    fn segment_of(&self, value: i64) -> usize {
+        self.starts.partition_point(|s| *s <= value) - 1
+    }
+
+    pub fn get(&self, value: i64) -> i64 {
+        value + self.offsets[self.segment_of(value)]
+    }
+
+    // The parts of `range` that fall in each segment, with that segment's offset
+    pub fn split(&self, range: Range) -> impl Iterator<Item = (Range, i64)> + '_ {
+        (self.segment_of(range.start)..self.starts.len())
+            .map(|i| self.segment(i))
+            .take_while(move |(segment, _)| segment.start < range.end)
+            .filter_map(move |(segment, offset)| Some((range.intersect(&segment).1?, offset)))
+    }
+
+    pub fn map_range(&self, range: Range) -> impl Iterator<Item = Range> + '_ {
+        self.split(range).map(|(r, offset)| r.offset(offset))
+    }
+
+    pub fn inverse(&self) -> Preimage {
+        let images = self
+            .segments()
+            .map(|(range, offset)| (range.offset(offset), -offset))
+            .collect::<Vec<_>>();
+        let mut starts = images
+            .iter()
+            .flat_map(|(image, _)| [image.start, image.end])
+            .chain([i64::MIN])
+            .filter(|start| *start < i64::MAX)
+            .collect::<Vec<_>>();
+        starts.sort_unstable();
+        starts.dedup();
+
+        let mut preimage = Preimage {
+            starts: Vec::new(),
+            offsets: Vec::new(),
+        };
+        for start in starts {
+            let mut offsets = images
+                .iter()
+                .filter(|(image, _)| image.start <= start && start < image.end)
+                .map(|(_, offset)| *offset)
+                .collect::<Vec<_>>();
+            offsets.sort_unstable();
+            if preimage.offsets.last() != Some(&offsets) {
+                preimage.starts.push(start);
+                preimage.offsets.push(offsets);
+            }
+        }
+        preimage
+    }
+}
+
+// The inverse of a `Piecewise` map. Tables need not be one-to-one, so each segment holds every
+// offset that leads back to a seed, and none where no seed lands.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Preimage {
+    starts: Vec<i64>,
+    offsets: Vec<Vec<i64>>,
+}
+
+impl Preimage {
+    pub fn segments(&self) -> impl Iterator<Item = (Range, &[i64])> + '_ {
+        let ends = self.starts[1..].iter().copied().chain([i64::MAX]);
+        std::iter::zip(&self.starts, ends)
+            .zip(&self.offsets)
+            .map(|((start, end), offsets)| (Range::new(*start, end), offsets.as_slice()))
+    }
+
+    pub fn get(&self, location: i64) -> impl Iterator<Item = i64> + '_ {
+        let i = self.starts.partition_point(|s| *s <= location) - 1;
+        self.offsets[i].iter().map(move |offset| location + offset)
+    }
+
+    // The seeds landing anywhere in `locations`, as ranges
+    pub fn map_range(&self, locations: Range) -> Vec<Range> {
+        let mut seeds = self
+            .segments()
+            .filter_map(|(segment, offsets)| Some((locations.intersect(&segment).1?, offsets)))
+            .flat_map(|(r, offsets)| offsets.iter().map(move |offset| r.offset(*offset)))
+            .collect::<Vec<_>>();
+        seeds.sort_unstable_by_key(|r| r.start);
+        seeds
+    }
+
+    // Pairs of locations and the ranges of `seeds` landing on them, lowest locations first
+    pub fn lowest<'a>(&'a self, seeds: &'a [Range]) -> impl Iterator<Item = (Range, Range)> + 'a {
+        self.segments().flat_map(move |(segment, offsets)| {
+            let mut found = offsets
+                .iter()
+                .flat_map(|offset| {
+                    let candidates = segment.offset(*offset);
+                    seeds.iter().filter_map(move |seeds| {
+                        let seeds = seeds.intersect(&candidates).1?;
+                        Some((seeds.offset(-offset), seeds))
+                    })
+                })
+                .collect::<Vec<_>>();
+            found.sort_unstable_by_key(|(locations, _)| locations.start);
+            found
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn test_intersects() {
+        assert!(!Range::intersects(
+            &Range { start: 1, end: 2 },
+            &Range { start: 3, end: 4 }
+        ));
+        assert!(!Range::intersects(
+            &Range { start: 1, end: 3 },
+            &Range { start: 3, end: 4 }
+        ));
+        assert!(Range::intersects(
+            &Range { start: 1, end: 4 },
+            &Range { start: 3, end: 4 }
+        ));
+        assert!(Range::intersects(
+            &Range { start: 1, end: 5 },
+            &Range { start: 3, end: 4 }
+        ));
+        assert!(Range::intersects(
+            &Range { start: 2, end: 5 },
+            &Range { start: 3, end: 4 }
+        ));
+        assert!(Range::intersects(
+            &Range { start: 3, end: 5 },
+            &Range { start: 3, end: 4 }
+        ));
+        assert!(!Range::intersects(
+            &Range { start: 4, end: 5 },
+            &Range { start: 3, end: 4 }
+        ));
+    }
+
+    #[test]
+    fn test_compose() {
+        let Almanac { seeds, tables, .. } = parse(include_str!("example.txt")).unwrap();
+        let almanac = Piecewise::compose(&tables);
+        assert_eq!(
+            vec![82, 43, 86, 35],
+            seeds.iter().map(|s| almanac.get(*s)).collect::<Vec<_>>()
+        );
+        for seed in -10..120 {
+            let stepwise = tables
+                .iter()
+                .fold(seed, |v, table| Piecewise::from_table(table).get(v));
+            assert_eq!(stepwise, almanac.get(seed));
+        }
+        assert_eq!(almanac, almanac.then(&Piecewise::identity()));
+    }
+
+    #[test]
+    fn test_inverse() {
+        let Almanac { seeds, tables, .. } = parse(include_str!("example.txt")).unwrap();
+        let almanac = Piecewise::compose(&tables);
+        let inverse = almanac.inverse();
+        for location in -10..120 {
+            let mut expected = (-200..200)
+                .filter(|seed| almanac.get(*seed) == location)
+                .collect::<Vec<_>>();
+            let mut seeds = inverse.get(location).collect::<Vec<_>>();
+            expected.sort_unstable();
+            seeds.sort_unstable();
+            assert_eq!(expected, seeds);
+        }
+        assert_eq!(
+            vec![Range::new(82, 83)],
+            inverse.map_range(Range::value(46))
+        );
+
+        let seeds = seeds_as_ranges(&seeds).unwrap();
+        let (locations, lowest) = inverse.lowest(&seeds).next().unwrap();
+        assert_eq!(
+            (Range::new(46, 56), Range::new(82, 92)),
+            (locations, lowest)
+        );
+        assert_eq!(
+            find_min_location(seeds.clone(), &almanac),
+            inverse.lowest(&seeds).next().unwrap().0.start
+        );
+    }
+
+    #[test]
+    fn test_categories() {
+        let almanac = parse(include_str!("example.txt")).unwrap();
+        assert_eq!(
+            Ok(vec![Range::new(46, 57), Range::new(78, 81)]),
+            almanac.convert("seed", "humidity", &[Range::new(79, 93)])
+        );
+
+        // Shuffling the maps does not change the answer
+        let (seeds, maps) = include_str!("example.txt").split_once("\n\n").unwrap();
+        let mut maps = maps.split_terminator("\n\n").collect::<Vec<_>>();
+        maps.reverse();
+        let shuffled = format!("{seeds}\n\n{}", maps.join("\n\n"));
+        assert_eq!(46, part2(&shuffled));
+        assert_eq!(
+            vec!["humidity", "location"],
+            parse(&shuffled)
+                .unwrap()
+                .categories()
+                .take(2)
+                .collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            Err("no conversion from `seed` to `colour`: the chain stops at `location`".to_string()),
+            almanac.map("seed", "colour").map(|_| ())
+        );
+        assert_eq!(
+            Err("no map converts `location`".to_string()),
+            almanac.map("location", "seed").map(|_| ())
+        );
+
+        let broken = "seeds: 1\n\na-to-b map:\n1 2 3\n\nb-to-c map:\n\nc-to-a map:\n";
+        assert_eq!(
+            Some("maps form a cycle: a -> b -> c -> a".to_string()),
+            parse(broken).err()
+        );
+        let forked = "seeds: 1\n\na-to-b map:\n\na-to-c map:\n";
+        assert_eq!(
+            Some("`a` is converted by two maps, to `b` and to `c`".to_string()),
+            parse(forked).err()
+        );
+        assert_eq!(
+            Some("a-to-b map: expected `<destination> <source> <length>`, found `1 2`".to_string()),
+            parse("seeds: 1\n\na-to-b map:\n1 2\n").err()
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let almanac = parse_strict(include_str!("example.txt")).unwrap();
+        assert!(almanac.tables.iter().all(|t| t.validate().is_empty()));
+        assert_eq!(
+            vec![Range::new(i64::MIN, 50), Range::new(100, i64::MAX)],
+            almanac.tables[0].gaps()
+        );
+
+        let input = "seeds: 1\n\n\
+            a-to-b map:\n\
+            0 10 10\n\
+            5 15 5\n\
+            100 12 2\n\
+            7 30 0\n\
+            0 9223372036854775800 100\n\
+            50 50 5";
+        let almanac = parse(input).unwrap();
+        let table = &almanac.tables[0];
+        assert_eq!(
+            vec![
+                Issue::Empty { entry: 4 },
+                Issue::Overflow { entry: 5 },
+                Issue::Overlap {
+                    first: Range::new(10, 20),
+                    second: Range::new(12, 14),
+                    conflicting: true
+                },
+                Issue::Overlap {
+                    first: Range::new(10, 20),
+                    second: Range::new(15, 20),
+                    conflicting: false
+                },
+            ],
+            table.validate()
+        );
+        assert_eq!(
+            vec![
+                Range::new(i64::MIN, 10),
+                Range::new(20, 50),
+                Range::new(55, i64::MAX)
+            ],
+            table.gaps()
+        );
+        assert_eq!(
+            Some("a-to-b map: entry 1 has negative length -1".to_string()),
+            parse("seeds: 1\n\na-to-b map:\n1 2 -1\n").err()
+        );
+        assert_eq!(
+            Err("seed range starting at 100 has negative length -50".to_string()),
+            seeds_as_ranges(&[1, 2, 100, -50])
+        );
+        assert_eq!(
+            Err(format!("seed range {} + 1 overflows i64", i64::MAX)),
+            seeds_as_ranges(&[i64::MAX, 1])
+        );
+        assert_eq!(
+            Err("seeds do not pair up into ranges".to_string()),
+            seeds_as_ranges(&[1, 2, 3])
+        );
+        assert_eq!(
+            Some(
+                "a-to-b map: entry 5 overflows i64 and is skipped\n\
+                a-to-b map: entries for 10..20 and 12..14 overlap with conflicting destinations"
+                    .to_string()
+            ),
+            parse_strict(input).err()
+        );
+
+        // Where entries overlap the first one wins
+        assert_eq!(
+            Ok(vec![Range::new(1, 5), Range::new(50, 60)]),
+            almanac.convert("a", "b", &[Range::new(11, 15), Range::new(50, 60)])
+        );
+    }
+
+    #[test]
+    fn part1_example() {
+        assert_eq!(35, part1(include_str!("example.txt")));
+    }
+
+    #[ignore]
+    #[test]
+    fn part1_verify() {
+        assert_eq!(910845529, part1(INPUT));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(46, part2(include_str!("example.txt")));
+    }
+
+    #[ignore]
+    #[test]
+    fn part2_verify() {
+        assert_eq!(77435348, part2(INPUT));
+    }
+}
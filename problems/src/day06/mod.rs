@@ -0,0 +1,454 @@
+use std::fmt::{Display, Formatter};
+use std::iter::zip;
+use std::ops::RangeInclusive;
+
+pub const INPUT: &str = include_str!("input.txt");
+
+// distance = (race time - press time) * speed
+// speed = press time
+// d = (tr - tp) * tp
+// d = tr * tp - tp^2
+// tp = (tr +- sqrt(tr * tr - 4 * d)) / 2
+
+pub fn part1(input: &str) -> u64 {
+    let races = parse(input).expect("races");
+    races.into_iter().map(|(t, d)| solve(t, d)).product::<u64>()
+}
+
+pub fn part2(input: &str) -> u64 {
+    let (time, distance) = parse_races2(input).expect("races");
+    solve(time, distance)
+}
+
+pub fn solve(race_time: i64, distance_to_beat: i64) -> u64 {
+    winning_holds(race_time, distance_to_beat)
+        .map_or(0, |holds| holds.start().abs_diff(*holds.end()) + 1)
+}
+
+// Worked in `i128`, where `tr * tr` cannot overflow for any `i64` race. The square root only gives
+// a first guess; the bounds are then moved until `wins` holds at the lower one and fails just
+// below it. The distance is symmetric about `tr / 2`, so the upper bound mirrors the lower one.
+pub fn winning_holds(race_time: i64, distance_to_beat: i64) -> Option<RangeInclusive<i64>> {
+    let (tr, d) = (i128::from(race_time), i128::from(distance_to_beat));
+    let wins = |tp: i128| tp * (tr - tp) > d;
+    if tr < 0 || !wins(tr / 2) {
+        return None;
+    }
+
+    let discriminant = tr * tr - 4 * d;
+    let mut lo = ((tr - discriminant.isqrt()) / 2).clamp(0, tr / 2);
+    while !wins(lo) {
+        lo += 1;
+    }
+    while lo > 0 && wins(lo - 1) {
+        lo -= 1;
+    }
+    let hi = tr - lo;
+    Some(lo as i64..=hi as i64)
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Model {
+    // Speed grows by `charge_rate` for every millisecond held, up to `top_speed`
+    Charged {
+        charge_rate: i64,
+        top_speed: Option<i64>,
+    },
+    // Speed is a polynomial in the hold time, coefficients lowest power first
+    Polynomial(Vec<i64>),
+}
+
+// Either way the boat keeps its speed for the rest of the race. Searches assume the distance rises
+// strictly to a peak and never rises again after it. That holds for any charged boat with a
+// non-negative charge rate and top speed, but not for every polynomial with non-negative
+// coefficients: a large constant term can make holding for no time at all beat a later peak. So
+// `optimum` and `winning_holds` refuse negative parameters, and polynomials they cannot show to
+// peak once in the race. Speeds and distances too large for `i128` are `None`.
+impl Model {
+    pub const PUZZLE: Self = Self::Charged {
+        charge_rate: 1,
+        top_speed: None,
+    };
+
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            Model::Charged { charge_rate, .. } if *charge_rate < 0 => {
+                Err(format!("charge rate {charge_rate} is negative"))
+            }
+            Model::Charged {
+                top_speed: Some(top),
+                ..
+            } if *top < 0 => Err(format!("top speed {top} is negative")),
+            Model::Polynomial(coefficients) => match coefficients.iter().find(|c| **c < 0) {
+                Some(c) => Err(format!("coefficient {c} is negative")),
+                None => Ok(()),
+            },
+            Model::Charged { .. } => Ok(()),
+        }
+    }
+
+    pub fn speed(&self, hold: i64) -> Option<i128> {
+        let hold = i128::from(hold);
+        match self {
+            Model::Charged {
+                charge_rate,
+                top_speed,
+            } => {
+                let speed = hold * i128::from(*charge_rate);
+                Some(top_speed.map_or(speed, |top| speed.min(i128::from(top))))
+            }
+            Model::Polynomial(coefficients) => {
+                coefficients.iter().rev().try_fold(0i128, |speed, c| {
+                    speed.checked_mul(hold)?.checked_add(i128::from(*c))
+                })
+            }
+        }
+    }
+
+    pub fn distance(&self, race_time: i64, hold: i64) -> Option<i128> {
+        self.speed(hold)?
+            .checked_mul(i128::from(race_time) - i128::from(hold))
+    }
+
+    // No hold goes faster than holding for the whole race, so if that speed kept for the whole
+    // race fits, every distance in the race does
+    fn checked(&self, race_time: i64) -> Result<impl Fn(i64) -> i128 + '_, String> {
+        self.validate()?;
+        if !self.peaks_once(race_time) {
+            return Err(format!(
+                "the distance in a {race_time} ms race may peak more than once"
+            ));
+        }
+        self.speed(race_time.max(0))
+            .and_then(|speed| speed.checked_mul(i128::from(race_time)))
+            .ok_or_else(|| format!("distances in a {race_time} ms race overflow i128"))?;
+        Ok(move |hold| {
+            self.distance(race_time, hold)
+                .expect("distances within the bound")
+        })
+    }
+
+    // The derivative of the distance `s(h) * (t - h)` for a speed `s(h) = sum(c[j] * h^j)` has
+    // the coefficients `(j + 1) * (t * c[j + 1] - c[j])`. By Descartes' rule of signs it has at
+    // most one positive root, so the distance at most one peak, if they change sign at most once.
+    // The rule only bounds the number of roots, so some models with a single peak are refused too.
+    fn peaks_once(&self, race_time: i64) -> bool {
+        let Model::Polynomial(c) = self else {
+            return true;
+        };
+        let t = i128::from(race_time.max(0));
+        let signs = (0..c.len())
+            .map(|j| t * c.get(j + 1).map_or(0, |c| i128::from(*c)) - i128::from(c[j]))
+            .filter(|a| *a != 0)
+            .map(|a| a > 0)
+            .collect::<Vec<_>>();
+        signs.windows(2).filter(|w| w[0] != w[1]).count() <= 1
+    }
+
+    // The shortest hold reaching the furthest distance, with that distance
+    pub fn optimum(&self, race_time: i64) -> Result<(i64, i128), String> {
+        let distance = self.checked(race_time)?;
+        let hold = first(0, race_time, |tp| {
+            tp == race_time || distance(tp + 1) <= distance(tp)
+        })
+        .expect("the last hold ends the search");
+        Ok((hold, distance(hold)))
+    }
+
+    pub fn winning_holds(
+        &self,
+        race_time: i64,
+        distance_to_beat: i64,
+    ) -> Result<Option<RangeInclusive<i64>>, String> {
+        if race_time < 0 {
+            return Ok(None);
+        }
+        let (optimum, furthest) = self.optimum(race_time)?;
+        let distance = self.checked(race_time)?;
+        let d = i128::from(distance_to_beat);
+        if furthest <= d {
+            return Ok(None);
+        }
+        let Some(lo) = first(0, optimum, |tp| distance(tp) > d) else {
+            return Ok(None);
+        };
+        let hi = first(optimum, race_time, |tp| distance(tp) <= d).map_or(race_time, |tp| tp - 1);
+        Ok(Some(lo..=hi))
+    }
+}
+
+// The smallest value in `lo..=hi` for which `pred` holds, for a `pred` that is false up to some
+// point and true from there on
+fn first(mut lo: i64, mut hi: i64, pred: impl Fn(i64) -> bool) -> Option<i64> {
+    if !pred(hi) {
+        return None;
+    }
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if pred(mid) {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    Some(lo)
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Report {
+    pub race_time: i64,
+    pub distance_to_beat: i64,
+    pub optimal_hold: i64,
+    pub optimal_distance: i128,
+    pub winning_holds: Option<RangeInclusive<i64>>,
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "race {} ms, record {}: best hold {} ms reaches {}, ",
+            self.race_time, self.distance_to_beat, self.optimal_hold, self.optimal_distance
+        )?;
+        match &self.winning_holds {
+            Some(holds) => write!(
+                f,
+                "{} winning holds from {} to {} ms",
+                holds.end() - holds.start() + 1,
+                holds.start(),
+                holds.end()
+            ),
+            None => write!(f, "no winning holds"),
+        }
+    }
+}
+
+pub fn report(races: &[(i64, i64)], model: &Model) -> Result<Vec<Report>, String> {
+    races
+        .iter()
+        .map(|&(race_time, distance_to_beat)| {
+            let (optimal_hold, optimal_distance) = model.optimum(race_time)?;
+            Ok(Report {
+                race_time,
+                distance_to_beat,
+                optimal_hold,
+                optimal_distance,
+                winning_holds: model.winning_holds(race_time, distance_to_beat)?,
+            })
+        })
+        .collect()
+}
+
+pub fn parse(input: &str) -> Option<Vec<(i64, i64)>> {
+    let (time, distance) = input.split_once('\n')?;
+    let time = time.strip_prefix("Time:")?.trim();
+    let distance = distance.strip_prefix("Distance:")?.trim();
+    let time = time
+        .split_whitespace()
+        .map(|t| t.parse::<i64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    let distance = distance
+        .split_whitespace()
+        .map(|t| t.parse::<i64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    let td = zip(time, distance).collect::<Vec<_>>();
+    Some(td)
+}
+
+pub fn parse_races2(input: &str) -> Option<(i64, i64)> {
+    let (time, distance) = input.split_once('\n')?;
+    let time = time
+        .strip_prefix("Time:")?
+        .replace(|c: char| c.is_ascii_whitespace(), "");
+    let distance = distance
+        .strip_prefix("Distance:")?
+        .replace(|c: char| c.is_ascii_whitespace(), "");
+    let time = time.parse::<i64>().ok()?;
+    let distance = distance.parse::<i64>().ok()?;
+    Some((time, distance))
+}
+
+#[test]
+fn part1_example() {
+    assert_eq!(288, part1(include_str!("example.txt")));
+}
+
+#[ignore]
+#[test]
+fn part1_verify() {
+    assert_eq!(588588, part1(INPUT));
+}
+
+#[test]
+fn part2_example() {
+    assert_eq!(71503, part2(include_str!("example.txt")));
+}
+
+#[ignore]
+#[test]
+fn part2_verify() {
+    assert_eq!(34655848, part2(INPUT));
+}
+
+#[test]
+fn winning_holds_brute_force() {
+    for tr in 0..60i64 {
+        for d in -2..=tr * tr / 4 + 2 {
+            let winning = (0..=tr).filter(|tp| tp * (tr - tp) > d).collect::<Vec<_>>();
+            let expected = winning.first().map(|lo| *lo..=*winning.last().unwrap());
+            assert_eq!(expected, winning_holds(tr, d), "race {tr} {d}");
+            assert_eq!(winning.len() as u64, solve(tr, d));
+        }
+    }
+}
+
+#[test]
+fn winning_holds_large_races() {
+    // Distances reached exactly by some hold make the discriminant a perfect square
+    for tr in [71530, 1 << 32, 3_037_000_499 * 2, i64::MAX - 1, i64::MAX] {
+        for tp in [1, 2, tr / 3, tr / 2 - 2] {
+            let Ok(d) = i64::try_from(i128::from(tp) * i128::from(tr - tp)) else {
+                continue;
+            };
+            for d in [d - 1, d, d + 1] {
+                let holds = winning_holds(tr, d).unwrap();
+                let wins = |tp: i64| i128::from(tp) * i128::from(tr - tp) > i128::from(d);
+                assert!(wins(*holds.start()) && wins(*holds.end()));
+                assert!(!wins(holds.start() - 1) && !wins(holds.end() + 1));
+            }
+            assert_eq!(Some(tp + 1..=tr - tp - 1), winning_holds(tr, d));
+        }
+    }
+    assert_eq!(None, winning_holds(10, 25));
+    assert_eq!(Some(5..=5), winning_holds(10, 24));
+}
+
+#[test]
+fn models_brute_force() {
+    let models = [
+        Model::PUZZLE,
+        Model::Charged {
+            charge_rate: 3,
+            top_speed: None,
+        },
+        Model::Charged {
+            charge_rate: 2,
+            top_speed: Some(9),
+        },
+        Model::Charged {
+            charge_rate: 1,
+            top_speed: Some(0),
+        },
+        Model::Polynomial(vec![]),
+        Model::Polynomial(vec![5]),
+        Model::Polynomial(vec![0, 1, 2]),
+        Model::Polynomial(vec![0, 0, 0, 1]),
+        Model::Polynomial(vec![3, 2, 1]),
+        Model::Polynomial(vec![40, 1]),
+    ];
+    for model in &models {
+        for tr in 0..40 {
+            let distances = (0..=tr)
+                .map(|tp| model.distance(tr, tp).unwrap())
+                .collect::<Vec<_>>();
+            let furthest = *distances.iter().max().unwrap();
+            let optimal_hold = distances.iter().position(|d| *d == furthest).unwrap() as i64;
+            assert_eq!(
+                Ok((optimal_hold, furthest)),
+                model.optimum(tr),
+                "{model:?} {tr}"
+            );
+
+            for d in [
+                -1,
+                0,
+                1,
+                7,
+                30,
+                100,
+                1000,
+                furthest as i64 - 1,
+                furthest as i64,
+            ] {
+                let winning = (0..=tr)
+                    .filter(|tp| distances[*tp as usize] > i128::from(d))
+                    .collect::<Vec<_>>();
+                let expected = winning.first().map(|lo| *lo..=*winning.last().unwrap());
+                assert_eq!(
+                    Ok(expected),
+                    model.winning_holds(tr, d),
+                    "{model:?} {tr} {d}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn puzzle_model_matches_solve() {
+    let races = parse(include_str!("example.txt")).unwrap();
+    let reports = report(&races, &Model::PUZZLE).unwrap();
+    assert_eq!(
+        vec![(3, 12), (7, 56), (15, 225)],
+        reports
+            .iter()
+            .map(|r| (r.optimal_hold, r.optimal_distance))
+            .collect::<Vec<_>>()
+    );
+    for (r, (t, d)) in zip(reports, races) {
+        assert_eq!(winning_holds(t, d), r.winning_holds);
+    }
+    assert_eq!(
+        Ok(winning_holds(i64::MAX, i64::MAX)),
+        Model::PUZZLE.winning_holds(i64::MAX, i64::MAX)
+    );
+}
+
+#[test]
+fn constant_terms() {
+    // Holding for no time at all goes 160 mm, further than the later peak of 145 mm at 3 ms
+    let model = Model::Polynomial(vec![20, 0, 1]);
+    assert_eq!(Some(160), model.distance(8, 0));
+    assert_eq!(Some(145), model.distance(8, 3));
+    let refused = Err("the distance in a 8 ms race may peak more than once".to_string());
+    assert_eq!(refused, model.optimum(8));
+    assert_eq!(refused.map(|_| None), model.winning_holds(8, 159));
+    assert!(Model::Polynomial(vec![20, 0, 0, 0, 2]).optimum(3).is_err());
+    // The sign rule is only a bound, so this one is refused although it never peaks after 0 ms
+    assert!(Model::Polynomial(vec![1, 0, 2]).optimum(1).is_err());
+}
+
+#[test]
+fn invalid_models() {
+    let negative = [
+        Model::Charged {
+            charge_rate: -1,
+            top_speed: None,
+        },
+        Model::Charged {
+            charge_rate: 1,
+            top_speed: Some(-2),
+        },
+        Model::Polynomial(vec![1, -3]),
+    ];
+    let errors = negative
+        .iter()
+        .map(|model| model.optimum(10).unwrap_err())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            "charge rate -1 is negative",
+            "top speed -2 is negative",
+            "coefficient -3 is negative"
+        ],
+        errors
+    );
+
+    let cubic = Model::Polynomial(vec![0, 0, 0, 1]);
+    assert!(cubic.winning_holds(1_000_000_000, 0).is_ok());
+    assert_eq!(None, cubic.distance(1_000_000_000_000, 500_000_000_000));
+    assert_eq!(
+        Err("distances in a 1000000000000 ms race overflow i128".to_string()),
+        cubic.winning_holds(1_000_000_000_000, 0)
+    );
+}
@@ -0,0 +1,573 @@
+#[cfg(test)]
+use adventofcode2023_common::random::XorShift;
+use std::cmp::Reverse;
+use std::fmt::Write as _;
+use std::io::{self, Write};
+
+pub const INPUT: &str = include_str!("input.txt");
+
+pub fn part1(input: &str) -> usize {
+    calculate_winnings(input, &Rules::part1()).unwrap_or_else(|e| panic!("{e}"))
+}
+
+pub fn part2(input: &str) -> usize {
+    calculate_winnings(input, &Rules::part2()).unwrap_or_else(|e| panic!("{e}"))
+}
+
+pub fn calculate_winnings(input: &str, rules: &Rules) -> Result<usize, String> {
+    let hands = parse(input, rules)?;
+
+    let mut keys = hands
+        .iter()
+        .map(|(hand, bid)| (rules.sort_key(hand), *bid))
+        .collect::<Vec<_>>();
+    // Identical hands tie; ordering them by bid keeps the winnings from depending on the sort
+    keys.sort_unstable();
+    Ok(keys
+        .into_iter()
+        .enumerate()
+        .map(|(rank, (_, bid))| (rank + 1) * bid)
+        .sum())
+}
+
+// Limits that let hands be classified with fixed-size arrays
+pub const MAX_ALPHABET: usize = 64;
+pub const MAX_HAND_SIZE: usize = 128;
+
+// Cards by their position in `Rules::alphabet`, weakest first
+pub type Hand = Vec<usize>;
+
+// A hand has a type when it holds groups of equal cards at least as large as `groups`, which are
+// kept largest first. `[3, 2]` is a full house, `[1]` any hand at all.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HandType {
+    pub name: String,
+    pub groups: Vec<usize>,
+}
+
+impl HandType {
+    pub fn new(name: &str, groups: &[usize]) -> Self {
+        let mut groups = groups.to_vec();
+        groups.sort_unstable_by_key(|g| Reverse(*g));
+        Self {
+            name: name.to_string(),
+            groups,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TieBreak {
+    // Hands of the same type compare card by card, in the order they were dealt
+    InOrder,
+    // Hands of the same type compare their strongest cards first
+    Sorted,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Rules {
+    // Cards from weakest to strongest
+    pub alphabet: Vec<char>,
+    pub hand_size: usize,
+    // Cards that stand in for whichever card makes the strongest hand type
+    pub wild: Vec<char>,
+    // Hand types from weakest to strongest; a hand takes the strongest type it has
+    pub ladder: Vec<HandType>,
+    pub tie_break: TieBreak,
+}
+
+impl Rules {
+    pub fn part1() -> Self {
+        Self {
+            alphabet: "23456789TJQKA".chars().collect(),
+            hand_size: 5,
+            wild: Vec::new(),
+            ladder: Self::standard_ladder(),
+            tie_break: TieBreak::InOrder,
+        }
+    }
+
+    // Jokers are wild, and the weakest card when breaking ties
+    pub fn part2() -> Self {
+        Self {
+            alphabet: "J23456789TQKA".chars().collect(),
+            wild: vec!['J'],
+            ..Self::part1()
+        }
+    }
+
+    pub fn standard_ladder() -> Vec<HandType> {
+        vec![
+            HandType::new("high card", &[1]),
+            HandType::new("one pair", &[2]),
+            HandType::new("two pairs", &[2, 2]),
+            HandType::new("three of a kind", &[3]),
+            HandType::new("full house", &[3, 2]),
+            HandType::new("four of a kind", &[4]),
+            HandType::new("five of a kind", &[5]),
+        ]
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.alphabet.is_empty() {
+            return Err("no cards".to_string());
+        }
+        for (i, c) in self.alphabet.iter().enumerate() {
+            if self.alphabet[..i].contains(c) {
+                return Err(format!("card `{c}` appears twice in the alphabet"));
+            }
+        }
+        for (i, c) in self.wild.iter().enumerate() {
+            if !self.alphabet.contains(c) {
+                return Err(format!("wild card `{c}` is not in the alphabet"));
+            }
+            if self.wild[..i].contains(c) {
+                return Err(format!("wild card `{c}` appears twice"));
+            }
+        }
+        if self.ladder.is_empty() {
+            return Err("no hand types".to_string());
+        }
+        if self.alphabet.len() > MAX_ALPHABET || self.hand_size > MAX_HAND_SIZE {
+            return Err(format!(
+                "at most {MAX_ALPHABET} cards and hands of {MAX_HAND_SIZE} are supported"
+            ));
+        }
+        let (type_bits, card_bits) = self.key_bits();
+        if type_bits + self.hand_size as u32 * card_bits > u128::BITS {
+            return Err(format!(
+                "hands of {} cards out of {} do not fit a {}-bit sort key",
+                self.hand_size,
+                self.alphabet.len(),
+                u128::BITS
+            ));
+        }
+        Ok(())
+    }
+
+    // Bits taken by the hand type and by each card in a sort key
+    fn key_bits(&self) -> (u32, u32) {
+        let bits = |n: usize| (usize::BITS - n.leading_zeros()).max(1);
+        (bits(self.ladder.len()), bits(self.alphabet.len() - 1))
+    }
+
+    pub fn card(&self, c: char) -> Option<usize> {
+        self.alphabet.iter().position(|a| *a == c)
+    }
+
+    fn is_wild(&self, card: usize) -> bool {
+        self.wild.contains(&self.alphabet[card])
+    }
+
+    pub fn parse_hand(&self, hand: &str) -> Result<Hand, String> {
+        let cards = hand
+            .chars()
+            .map(|c| self.card(c).ok_or_else(|| format!("unknown card `{c}`")))
+            .collect::<Result<Hand, _>>()?;
+        if cards.len() != self.hand_size {
+            return Err(format!(
+                "hand `{hand}` has {} cards, not {}",
+                cards.len(),
+                self.hand_size
+            ));
+        }
+        Ok(cards)
+    }
+
+    // The sizes of the groups of equal cards other than wild ones, largest first, followed by
+    // zeroes, and the number of wild cards. Classifying and keying assume rules that passed
+    // `validate`, as `parse` checks once for all its hands, and a hand parsed by them.
+    fn groups(&self, hand: &[usize]) -> ([u8; MAX_ALPHABET], usize) {
+        debug_assert!(
+            hand.len() == self.hand_size && hand.iter().all(|c| *c < self.alphabet.len()),
+            "hand {hand:?} does not fit the rules"
+        );
+        let mut counts = [0u8; MAX_ALPHABET];
+        let mut wild = 0;
+        for card in hand {
+            if self.is_wild(*card) {
+                wild += 1;
+            } else {
+                counts[*card] += 1;
+            }
+        }
+        counts.sort_unstable_by_key(|n| Reverse(*n));
+        (counts, wild)
+    }
+
+    // Wild cards needed to give a hand with `groups` the groups of `hand_type`. Matching the
+    // largest groups wanted with the largest groups held needs the fewest; groups not yet held
+    // need cards not yet in the hand.
+    fn wild_needed(&self, hand_type: &HandType, groups: &[u8]) -> Option<usize> {
+        let held = groups.iter().take_while(|n| **n > 0).count();
+        let unused = self.alphabet.len() - self.wild.len() - held;
+        if hand_type.groups.len() > held + unused {
+            return None;
+        }
+        Some(
+            std::iter::zip(&hand_type.groups, groups)
+                .map(|(wanted, held)| wanted.saturating_sub(usize::from(*held)))
+                .sum(),
+        )
+    }
+
+    // The position of the hand's type in the ladder
+    pub fn classify(&self, hand: &[usize]) -> Option<usize> {
+        let (groups, wild) = self.groups(hand);
+        self.ladder
+            .iter()
+            .rposition(|t| self.wild_needed(t, &groups).is_some_and(|n| n <= wild))
+    }
+
+    // The hand type above the cards in tie-break order, so that keys order hands by strength. A
+    // hand of no type sorts below all others.
+    pub fn sort_key(&self, hand: &[usize]) -> u128 {
+        let (_, card_bits) = self.key_bits();
+        let hand_type = self.classify(hand).map_or(0, |t| t as u128 + 1);
+        let mut cards = [0; MAX_HAND_SIZE];
+        let cards = &mut cards[..hand.len()];
+        cards.copy_from_slice(hand);
+        if self.tie_break == TieBreak::Sorted {
+            cards.sort_unstable_by_key(|c| Reverse(*c));
+        }
+        cards
+            .iter()
+            .fold(hand_type, |key, card| key << card_bits | *card as u128)
+    }
+
+    // The hand with each wild card replaced by the card it stands in for, or `None` for a hand
+    // without wild cards. Wild cards first make up the groups of the hand's type, as
+    // `wild_needed` counts them, with the strongest cards that will do; any left over join the
+    // largest group.
+    pub fn substitute(&self, hand: &[usize]) -> Option<Hand> {
+        if !hand.iter().any(|c| self.is_wild(*c)) {
+            return None;
+        }
+        let hand_type = &self.ladder[self.classify(hand)?];
+        let mut held = (0..self.alphabet.len())
+            .filter(|c| !self.is_wild(*c))
+            .map(|c| (hand.iter().filter(|h| **h == c).count(), c))
+            .collect::<Vec<_>>();
+        held.sort_unstable_by_key(|g| Reverse(*g));
+
+        let mut stand_ins = std::iter::zip(&hand_type.groups, &held)
+            .flat_map(|(wanted, (n, c))| std::iter::repeat_n(*c, wanted.saturating_sub(*n)))
+            .chain(std::iter::repeat(held.first()?.1));
+        Some(
+            hand.iter()
+                .map(|c| {
+                    if self.is_wild(*c) {
+                        stand_ins.next().unwrap()
+                    } else {
+                        *c
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    pub fn hand_name(&self, hand: &[usize]) -> String {
+        hand.iter().map(|c| self.alphabet[*c]).collect()
+    }
+}
+
+// One line of the ranking, weakest hand first
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Ranked {
+    pub rank: usize,
+    pub hand: String,
+    pub hand_type: String,
+    pub bid: usize,
+    pub winnings: usize,
+    // The hand the wild cards made, when there are any
+    pub substitution: Option<String>,
+}
+
+pub fn report(input: &str, rules: &Rules) -> Result<Vec<Ranked>, String> {
+    let mut hands = parse(input, rules)?;
+    hands.sort_by_cached_key(|(hand, bid)| (rules.sort_key(hand), *bid));
+    Ok(hands
+        .into_iter()
+        .enumerate()
+        .map(|(i, (hand, bid))| Ranked {
+            rank: i + 1,
+            hand: rules.hand_name(&hand),
+            hand_type: rules.ladder[rules.classify(&hand).expect("typed hand")]
+                .name
+                .clone(),
+            bid,
+            winnings: (i + 1) * bid,
+            substitution: rules.substitute(&hand).map(|h| rules.hand_name(&h)),
+        })
+        .collect())
+}
+
+pub fn write_table(ranking: &[Ranked], w: &mut impl Write) -> io::Result<()> {
+    let hand_width = ranking
+        .iter()
+        .map(|r| r.hand.len())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    let type_width = ranking
+        .iter()
+        .map(|r| r.hand_type.len())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    writeln!(
+        w,
+        "{:>6}  {:hand_width$}  {:type_width$}  {:>6}  {:>10}  played as",
+        "rank", "hand", "type", "bid", "winnings"
+    )?;
+    for r in ranking {
+        let line = format!(
+            "{:>6}  {:hand_width$}  {:type_width$}  {:>6}  {:>10}  {}",
+            r.rank,
+            r.hand,
+            r.hand_type,
+            r.bid,
+            r.winnings,
+            r.substitution.as_deref().unwrap_or("")
+        );
+        writeln!(w, "{}", line.trim_end())?;
+    }
+    let total = ranking.iter().map(|r| r.winnings).sum::<usize>();
+    writeln!(w, "total winnings: {total}")
+}
+
+pub fn write_json(ranking: &[Ranked], w: &mut impl Write) -> io::Result<()> {
+    writeln!(w, "[")?;
+    for (i, r) in ranking.iter().enumerate() {
+        let substitution = r
+            .substitution
+            .as_deref()
+            .map_or("null".to_string(), json_string);
+        let separator = if i + 1 < ranking.len() { "," } else { "" };
+        writeln!(
+            w,
+            "  {{\"rank\": {}, \"hand\": {}, \"type\": {}, \"bid\": {}, \"winnings\": {}, \"substitution\": {}}}{separator}",
+            r.rank,
+            json_string(&r.hand),
+            json_string(&r.hand_type),
+            r.bid,
+            r.winnings,
+            substitution
+        )?;
+    }
+    writeln!(w, "]")
+}
+
+fn json_string(s: &str) -> String {
+    let mut json = String::from('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if c.is_control() => write!(json, "\\u{:04x}", c as u32).unwrap(),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+pub fn parse(input: &str, rules: &Rules) -> Result<Vec<(Hand, usize)>, String> {
+    rules.validate()?;
+    input
+        .lines()
+        .filter_map(|hand_bid| hand_bid.split_once(' '))
+        .map(|(hand, bid)| {
+            let hand = rules.parse_hand(hand)?;
+            if rules.classify(&hand).is_none() {
+                return Err(format!("hand {hand:?} has none of the hand types"));
+            }
+            let bid = bid
+                .parse::<usize>()
+                .map_err(|e| format!("bid `{bid}`: {e}"))?;
+            Ok((hand, bid))
+        })
+        .collect()
+}
+
+#[test]
+fn part1_example() {
+    assert_eq!(6440, part1(include_str!("example.txt")));
+}
+
+#[ignore]
+#[test]
+fn part1_verify() {
+    assert_eq!(250058342, part1(INPUT));
+}
+
+#[test]
+fn part2_example() {
+    assert_eq!(5905, part2(include_str!("example.txt")));
+}
+
+#[ignore]
+#[test]
+fn part2_verify() {
+    assert_eq!(250506580, part2(INPUT));
+}
+
+#[test]
+fn classify_any_hand_size() {
+    let rules = Rules::part2();
+    let name = |hand: &str| {
+        let hand = rules.parse_hand(hand).unwrap();
+        rules.ladder[rules.classify(&hand).unwrap()].name.as_str()
+    };
+    assert_eq!("five of a kind", name("JJJJJ"));
+    assert_eq!("four of a kind", name("KTJJT"));
+    assert_eq!("full house", name("2J233"));
+    assert_eq!("one pair", name("2J345"));
+    assert_eq!("high card", name("23456"));
+
+    let seven = Rules {
+        hand_size: 7,
+        ladder: vec![
+            HandType::new("nothing", &[]),
+            HandType::new("two triples", &[3, 3]),
+            HandType::new("five of a kind", &[5]),
+            HandType::new("three pairs", &[2, 2, 2]),
+        ],
+        ..Rules::part2()
+    };
+    let name = |hand: &str| {
+        let hand = seven.parse_hand(hand).unwrap();
+        seven.ladder[seven.classify(&hand).unwrap()].name.as_str()
+    };
+    // Adding both jokers to the largest group would make none of these types
+    assert_eq!("three pairs", name("AAKKJJ2"));
+    assert_eq!("five of a kind", name("AAAAJ23"));
+    assert_eq!("two triples", name("AAAKKK2"));
+    assert_eq!("nothing", name("AKQT987"));
+    assert_eq!(
+        Err("hand `AAAA` has 4 cards, not 7".to_string()),
+        seven.parse_hand("AAAA")
+    );
+}
+
+#[test]
+fn tie_break_rules() {
+    let sorted = Rules {
+        tie_break: TieBreak::Sorted,
+        ..Rules::part1()
+    };
+    let key = |rules: &Rules, hand: &str| rules.sort_key(&rules.parse_hand(hand).unwrap());
+    assert!(key(&Rules::part1(), "A2345") > key(&Rules::part1(), "KQT98"));
+    assert!(key(&sorted, "2345A") == key(&sorted, "A2345"));
+    assert!(key(&sorted, "A2345") > key(&sorted, "KQT98"));
+    assert!(key(&sorted, "6A345") > key(&sorted, "A2346"));
+    assert!(key(&Rules::part1(), "6A345") < key(&Rules::part1(), "A2346"));
+    assert_eq!(
+        Err("wild card `X` is not in the alphabet".to_string()),
+        Rules {
+            wild: vec!['X'],
+            ..Rules::part1()
+        }
+        .validate()
+    );
+    assert_eq!(
+        Err("wild card `J` appears twice".to_string()),
+        Rules {
+            wild: vec!['J', 'J'],
+            ..Rules::part2()
+        }
+        .validate()
+    );
+    assert_eq!(
+        Err("no cards".to_string()),
+        Rules {
+            alphabet: Vec::new(),
+            wild: Vec::new(),
+            ..Rules::part1()
+        }
+        .validate()
+    );
+}
+
+#[test]
+fn sort_keys_order_like_comparisons() {
+    let mut random = XorShift::new(0x2545_f491_4f6c_dd1d);
+    for rules in [Rules::part1(), Rules::part2()] {
+        let hands = (0..200)
+            .map(|_| (0..5).map(|_| random.below(13)).collect::<Hand>())
+            .collect::<Vec<_>>();
+        for a in &hands {
+            for b in &hands[..50] {
+                let compared = (rules.classify(a), a).cmp(&(rules.classify(b), b));
+                assert_eq!(compared, rules.sort_key(a).cmp(&rules.sort_key(b)));
+            }
+        }
+    }
+
+    let huge = Rules {
+        hand_size: 40,
+        ..Rules::part1()
+    };
+    assert_eq!(
+        Err("hands of 40 cards out of 13 do not fit a 128-bit sort key".to_string()),
+        huge.validate()
+    );
+}
+
+#[test]
+fn substitutions() {
+    let rules = Rules::part2();
+    let substitute = |hand: &str| {
+        let hand = rules.substitute(&rules.parse_hand(hand).unwrap())?;
+        Some(rules.hand_name(&hand))
+    };
+    assert_eq!(None, substitute("32T3K"));
+    assert_eq!(Some("KTTTT".to_string()), substitute("KTJJT"));
+    assert_eq!(Some("QQQQA".to_string()), substitute("QQQJA"));
+    assert_eq!(Some("AAAAA".to_string()), substitute("JJJJJ"));
+    assert_eq!(Some("2K3K5".to_string()), substitute("2J3K5"));
+
+    // Without wild cards, the substituted hand has the type the wild cards gave the original
+    let plain = Rules {
+        wild: Vec::new(),
+        ..Rules::part2()
+    };
+    let mut random = XorShift::new(0x2545_f491_4f6c_dd1d);
+    for _ in 0..2000 {
+        let hand = (0..5).map(|_| random.below(13)).collect::<Hand>();
+        let substituted = rules.substitute(&hand).unwrap_or(hand.clone());
+        assert_eq!(rules.classify(&hand), plain.classify(&substituted));
+    }
+}
+
+#[test]
+fn ranking_report() {
+    let ranking = report(include_str!("example.txt"), &Rules::part2()).unwrap();
+    assert_eq!(5905, ranking.iter().map(|r| r.winnings).sum::<usize>());
+    assert_eq!(
+        Ranked {
+            rank: 5,
+            hand: "KTJJT".to_string(),
+            hand_type: "four of a kind".to_string(),
+            bid: 220,
+            winnings: 1100,
+            substitution: Some("KTTTT".to_string()),
+        },
+        ranking[4]
+    );
+
+    let mut json = Vec::new();
+    write_json(&ranking[..1], &mut json).unwrap();
+    assert_eq!(
+        "[\n  {\"rank\": 1, \"hand\": \"32T3K\", \"type\": \"one pair\", \"bid\": 765, \"winnings\": 765, \"substitution\": null}\n]\n",
+        String::from_utf8(json).unwrap()
+    );
+    assert_eq!("\"a\\\"b\\\\\\u000a\"", json_string("a\"b\\\n"));
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "does not fit the rules")]
+fn sort_key_checks_hands() {
+    Rules::part1().sort_key(&[0; MAX_HAND_SIZE + 1]);
+}
@@ -0,0 +1,789 @@
+use adventofcode2023_common::graph::{bfs_distances, strongly_connected_components};
+use adventofcode2023_common::interner::{pack, MAX_PACKED_LEN};
+#[cfg(test)]
+use adventofcode2023_common::random::XorShift;
+use std::collections::HashMap;
+use std::io::{self, Write};
+use Step::{Left, Right};
+
+pub const INPUT: &str = include_str!("input.txt");
+
+pub fn part1(input: &str) -> usize {
+    let input = parse(input, |s| s == "AAA", |s| s == "ZZZ").unwrap_or_else(|e| panic!("{e}"));
+    solve(input).unwrap_or_else(|e| panic!("{e}"))
+}
+
+pub fn part2(input: &str) -> usize {
+    let input =
+        parse(input, |s| s.ends_with('A'), |s| s.ends_with('Z')).unwrap_or_else(|e| panic!("{e}"));
+    solve(input).unwrap_or_else(|e| panic!("{e}"))
+}
+
+pub struct Input<'a> {
+    pub instructions: Vec<Step>,
+    pub starts: Vec<usize>,
+    pub nodes: Vec<([usize; 2], bool)>,
+    pub names: Vec<&'a str>,
+    // Node indices by packed name
+    index: HashMap<u64, usize>,
+}
+
+impl Input<'_> {
+    pub fn name(&self, node: usize) -> &str {
+        self.names.get(node).copied().unwrap_or("?")
+    }
+
+    pub fn node(&self, name: &str) -> Option<usize> {
+        self.index.get(&pack(name)?).copied()
+    }
+
+    fn neighbours(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.nodes[node].0.into_iter()
+    }
+
+    fn is_terminal(&self, node: usize) -> bool {
+        self.nodes[node].1
+    }
+
+    pub fn components(&self) -> Vec<Vec<usize>> {
+        strongly_connected_components(0..self.names.len(), |n| self.neighbours(*n))
+    }
+
+    // Starts whose walk following the instructions never stands on a terminal node
+    pub fn unreachable_starts(&self) -> Vec<usize> {
+        let jumps = Jumps::new(&self.instructions, &self.nodes);
+        self.starts
+            .iter()
+            .copied()
+            .filter(|start| jumps.cycle(*start).terminals.is_empty())
+            .collect()
+    }
+
+    // Whether any path leads from `start` to a terminal node, whatever the instructions
+    fn has_path_to_terminal(&self, start: usize) -> bool {
+        let reachable = bfs_distances([start], |n| self.neighbours(*n));
+        reachable.keys().any(|n| self.is_terminal(*n))
+    }
+
+    // Starts are filled green and terminal nodes red, or yellow for nodes that are both
+    pub fn write_dot(&self, w: &mut impl Write) -> io::Result<()> {
+        writeln!(w, "digraph network {{")?;
+        writeln!(w, "  node [shape=circle];")?;
+        for (node, name) in self.names.iter().enumerate() {
+            let colour = match (self.starts.contains(&node), self.is_terminal(node)) {
+                (true, true) => "gold",
+                (true, false) => "palegreen",
+                (false, true) => "salmon",
+                (false, false) => continue,
+            };
+            let shape = if self.is_terminal(node) {
+                "doublecircle"
+            } else {
+                "circle"
+            };
+            writeln!(
+                w,
+                "  {} [shape={shape}, style=filled, fillcolor={colour}];",
+                dot_id(name)
+            )?;
+        }
+        for (node, name) in self.names.iter().enumerate() {
+            let [left, right] = &self.nodes[node].0;
+            let edges = if left == right {
+                vec![(left, "L/R")]
+            } else {
+                vec![(left, "L"), (right, "R")]
+            };
+            for (next, label) in edges {
+                writeln!(
+                    w,
+                    "  {} -> {} [label=\"{label}\"];",
+                    dot_id(name),
+                    dot_id(self.name(*next))
+                )?;
+            }
+        }
+        writeln!(w, "}}")
+    }
+
+    pub fn write_analysis(&self, w: &mut impl Write) -> io::Result<()> {
+        let components = self.components();
+        let mut sizes = components
+            .iter()
+            .map(Vec::len)
+            .filter(|n| *n > 1)
+            .collect::<Vec<_>>();
+        sizes.sort_unstable_by(|a, b| b.cmp(a));
+        writeln!(
+            w,
+            "{} nodes in {} strongly connected components; larger than one node: {sizes:?}",
+            self.names.len(),
+            components.len()
+        )?;
+
+        let unreachable = self.unreachable_starts();
+        let jumps = Jumps::new(&self.instructions, &self.nodes);
+        for start in &self.starts {
+            let name = self.name(*start);
+            if unreachable.contains(start) {
+                if self.has_path_to_terminal(*start) {
+                    writeln!(w, "{name}: the instructions never lead to a terminal node")?;
+                } else {
+                    writeln!(w, "{name}: no path to a terminal node")?;
+                }
+                continue;
+            }
+            let cycle = jumps.cycle(*start);
+            let (once, repeating) = cycle
+                .terminals
+                .iter()
+                .partition::<Vec<&usize>, _>(|t| **t < cycle.prefix);
+            write!(
+                w,
+                "{name}: prefix {}, period {}, terminal once at {once:?}, then at {repeating:?}",
+                cycle.prefix, cycle.period
+            )?;
+            if repeating.is_empty() {
+                writeln!(w)?;
+            } else {
+                writeln!(w, " and every {} steps after", cycle.period)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn dot_id(name: &str) -> String {
+    format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+// The first step at which every ghost stands on a terminal node
+pub fn solve(input: Input) -> Result<usize, String> {
+    let jumps = Jumps::new(&input.instructions, &input.nodes);
+    let cycles = input
+        .starts
+        .iter()
+        .map(|start| {
+            let cycle = jumps.cycle(*start);
+            if cycle.terminals.is_empty() {
+                return Err(format!(
+                    "the ghost starting at {} never reaches a terminal node",
+                    input.name(*start)
+                ));
+            }
+            Ok(cycle)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let Some(longest) = cycles.iter().max_by_key(|c| c.prefix) else {
+        return Err("no ghosts".to_string());
+    };
+
+    // Meetings before every ghost has entered its cycle are one-off hits of the ghost that takes
+    // longest to get there
+    let early = longest
+        .terminals
+        .iter()
+        .copied()
+        .filter(|t| *t < longest.prefix)
+        .find(|t| cycles.iter().all(|c| c.is_terminal_at(*t)));
+    if let Some(t) = early {
+        return Ok(t);
+    }
+
+    // Afterwards each ghost is on a terminal at the steps matching one of its residues. Every ghost
+    // multiplies the combinations to check by its terminals in the cycle.
+    let mut residues = vec![(0u128, 1u128)];
+    for cycle in &cycles {
+        let period = cycle.period as u128;
+        let repeating = cycle
+            .terminals
+            .iter()
+            .filter(|t| **t >= cycle.prefix)
+            .count();
+        if residues.len().saturating_mul(repeating) > MAX_RESIDUES {
+            return Err(format!(
+                "more than {MAX_RESIDUES} combinations of the ghosts' terminal steps to check"
+            ));
+        }
+        residues = residues
+            .iter()
+            .flat_map(|&(a, n)| {
+                cycle
+                    .terminals
+                    .iter()
+                    .filter(|t| **t >= cycle.prefix)
+                    .filter_map(move |t| crt(a, n, *t as u128 % period, period))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        residues.sort_unstable();
+        residues.dedup();
+    }
+
+    let start = longest.prefix as u128;
+    residues
+        .into_iter()
+        .map(|(a, n)| a + start.saturating_sub(a).div_ceil(n) * n)
+        .min()
+        .ok_or_else(|| "the ghosts are never all on terminal nodes at once".to_string())?
+        .try_into()
+        .map_err(|_| "the ghosts meet too far away to count".to_string())
+}
+
+// A ghost is back where it was once it stands on the same node at the same point in the
+// instructions. `terminals` lists every step on a terminal node up to that point: those before
+// `prefix` happen once, those after repeat every `period` steps.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Cycle {
+    pub prefix: usize,
+    pub period: usize,
+    pub terminals: Vec<usize>,
+}
+
+impl Cycle {
+    pub fn is_terminal_at(&self, step: usize) -> bool {
+        if step < self.prefix {
+            self.terminals.contains(&step)
+        } else {
+            let step = self.prefix + (step - self.prefix) % self.period;
+            self.terminals.contains(&step)
+        }
+    }
+}
+
+pub fn find_cycle(instructions: &[Step], start: usize, nodes: &[([usize; 2], bool)]) -> Cycle {
+    Jumps::new(instructions, nodes).cycle(start)
+}
+
+// Where each node leads after the whole instruction string, and the steps along the way that
+// are on a terminal node, so that walks can go a block of instructions at a time
+pub struct Jumps<'a> {
+    instructions: &'a [Step],
+    nodes: &'a [([usize; 2], bool)],
+    ends: Vec<u32>,
+    terminals: Vec<Vec<u32>>,
+}
+
+impl<'a> Jumps<'a> {
+    pub fn new(instructions: &'a [Step], nodes: &'a [([usize; 2], bool)]) -> Self {
+        let (ends, terminals) = (0..nodes.len())
+            .map(|start| {
+                let mut terminals = Vec::new();
+                let mut here = start;
+                for (i, step) in instructions.iter().enumerate() {
+                    if nodes[here].1 {
+                        terminals.push(i as u32);
+                    }
+                    here = next(nodes, here, step);
+                }
+                (here as u32, terminals)
+            })
+            .unzip();
+        Jumps {
+            instructions,
+            nodes,
+            ends,
+            terminals,
+        }
+    }
+
+    pub fn count_steps(&self, start: usize) -> Option<usize> {
+        let mut here = start;
+        // A walk without a terminal node for more blocks than there are nodes never finds one
+        for block in 0..=self.nodes.len() {
+            if let Some(i) = self.terminals[here].first() {
+                return Some(block * self.instructions.len() + *i as usize);
+            }
+            here = self.ends[here] as usize;
+        }
+        None
+    }
+
+    // The walk repeats once a block starts on the same node again. That may have happened
+    // partway through the block before, where the two walks leading into it first meet.
+    pub fn cycle(&self, start: usize) -> Cycle {
+        let len = self.instructions.len();
+        let mut seen = vec![usize::MAX; self.nodes.len()];
+        let mut blocks = Vec::new();
+        let mut here = start;
+        while seen[here] == usize::MAX {
+            seen[here] = blocks.len();
+            blocks.push(here);
+            here = self.ends[here] as usize;
+        }
+        let period = (blocks.len() - seen[here]) * len;
+        let prefix = match seen[here].checked_sub(1) {
+            None => 0,
+            Some(block) => {
+                let (mut a, mut b) = (blocks[block], blocks[block + period / len]);
+                let mut meet = 0;
+                while meet < len && a != b {
+                    a = next(self.nodes, a, &self.instructions[meet]);
+                    b = next(self.nodes, b, &self.instructions[meet]);
+                    meet += 1;
+                }
+                block * len + meet
+            }
+        };
+
+        let terminals = blocks
+            .iter()
+            .enumerate()
+            .flat_map(|(block, node)| {
+                self.terminals[*node]
+                    .iter()
+                    .map(move |i| block * len + *i as usize)
+            })
+            .filter(|t| *t < prefix + period)
+            .collect();
+        Cycle {
+            prefix,
+            period,
+            terminals,
+        }
+    }
+}
+
+fn next(nodes: &[([usize; 2], bool)], here: usize, step: &Step) -> usize {
+    match step {
+        Left => nodes[here].0[0],
+        Right => nodes[here].0[1],
+    }
+}
+
+pub const MAX_RESIDUES: usize = 1 << 20;
+
+// Combines `x = a (mod n)` with `x = b (mod m)`, for `a < n` and `b < m`, for moduli that need
+// not be coprime. `None` when there is no such `x`, an error when the arithmetic overflows `i128`.
+fn crt(a: u128, n: u128, b: u128, m: u128) -> Option<Result<(u128, u128), String>> {
+    let too_long = || "the ghosts' cycles are too long to combine".to_string();
+    let (Ok(n), Ok(m), Ok(a), Ok(b)) = (
+        i128::try_from(n),
+        i128::try_from(m),
+        i128::try_from(a),
+        i128::try_from(b),
+    ) else {
+        return Some(Err(too_long()));
+    };
+    let (g, p, _) = extended_gcd(n, m);
+    let diff = b - a;
+    if diff % g != 0 {
+        return None;
+    }
+    let m_g = m / g;
+    let combined = (n / g).checked_mul(m).and_then(|lcm| {
+        let k = (diff / g % m_g).checked_mul(p % m_g)?.rem_euclid(m_g);
+        let x = n.checked_mul(k)?.checked_add(a)?;
+        Some((x.rem_euclid(lcm) as u128, lcm as u128))
+    });
+    Some(combined.ok_or_else(too_long))
+}
+
+// `(g, x, y)` with `a * x + b * y = g = gcd(a, b)`
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        (a, 1, 0)
+    } else {
+        let (g, x, y) = extended_gcd(b, a % b);
+        (g, y, x - a / b * y)
+    }
+}
+
+#[repr(u8)]
+#[derive(Debug)]
+pub enum Step {
+    Left,
+    Right,
+}
+
+fn parse_instruction(instructions: &str) -> Result<Vec<Step>, String> {
+    instructions
+        .chars()
+        .map(|c: char| match c {
+            'L' => Ok(Left),
+            'R' => Ok(Right),
+            _ => Err(format!("unknown instruction {c:?}")),
+        })
+        .collect()
+}
+
+// Nodes are found by their names packed into integers
+pub fn parse<'a>(
+    input: &'a str,
+    is_start: impl Fn(&str) -> bool,
+    is_terminal: impl Fn(&str) -> bool,
+) -> Result<Input<'a>, String> {
+    let (instructions, nodes) = input
+        .split_once("\n\n")
+        .ok_or("expected instructions and nodes separated by a blank line")?;
+    // Node lines are numbered from the start of the input, after the instructions and blank line
+    let first_line = instructions.lines().count() + 2;
+    let instructions = parse_instruction(instructions)?;
+    let mut names = Vec::new();
+    let mut index = HashMap::new();
+    let mut node_index = Vec::new();
+    let mut defined_on = HashMap::new();
+    let mut starts = Vec::new();
+
+    // Nodes named only as a destination lead nowhere: a ghost reaching one stays there for good
+    let absorbing = |node: usize| ([node, node], false);
+    let mut intern = |name: &'a str| {
+        let packed = pack(name)
+            .ok_or_else(|| format!("node name {name:?} does not fit in {MAX_PACKED_LEN} bytes"))?;
+        Ok::<_, String>(*index.entry(packed).or_insert_with(|| {
+            names.push(name);
+            names.len() - 1
+        }))
+    };
+
+    for (line_number, line) in (first_line..).zip(nodes.lines()) {
+        let (from, left, right) = line
+            .split_once(" = ")
+            .and_then(|(from, left_right)| {
+                let (left, right) = left_right
+                    .strip_prefix('(')?
+                    .strip_suffix(')')?
+                    .split_once(", ")?;
+                Some((from, left, right))
+            })
+            .ok_or_else(|| format!("expected `<node> = (<left>, <right>)`, found {line:?}"))?;
+
+        let is_start = is_start(from);
+        let is_terminal = is_terminal(from);
+        let name = from;
+        let from = intern(from)?;
+        if let Err(e) = defined_on.try_insert(from, line_number) {
+            return Err(format!(
+                "line {line_number}: node {name} is already defined on line {}",
+                e.entry.get()
+            ));
+        }
+        if is_start {
+            starts.push(from);
+        }
+
+        let left = intern(left)?;
+        let right = intern(right)?;
+
+        while node_index.len() <= from {
+            node_index.push(absorbing(node_index.len()));
+        }
+
+        node_index[from] = ([left, right], is_terminal);
+    }
+    while node_index.len() < names.len() {
+        node_index.push(absorbing(node_index.len()));
+    }
+
+    Ok(Input {
+        instructions,
+        starts,
+        nodes: node_index,
+        names,
+        index,
+    })
+}
+
+#[test]
+fn part1_example1() {
+    assert_eq!(2, part1(include_str!("example1.txt")));
+}
+
+#[test]
+fn part1_example2() {
+    assert_eq!(6, part1(include_str!("example2.txt")));
+}
+
+#[ignore]
+#[test]
+fn part1_verify() {
+    assert_eq!(12169, part1(INPUT));
+}
+
+#[test]
+fn part2_example() {
+    assert_eq!(6, part2(include_str!("example3.txt")));
+}
+
+#[ignore]
+#[test]
+fn part2_verify() {
+    assert_eq!(12030780859469, part2(INPUT));
+}
+
+#[test]
+fn ghosts_with_prefixes() {
+    let network = |nodes: &str| format!("L\n\n{}", nodes.trim().replace("; ", "\n"));
+
+    // Ghost A is on terminals at 3, 6, 9, ..., ghost B at 1, 5, 9, ...; the first arrivals alone
+    // would give 3
+    let input = network(
+        "AAA = (ABB, ABB); ABB = (ACC, ACC); ACC = (AZZ, AZZ); AZZ = (ABB, ABB); \
+         BAA = (BZZ, BZZ); BZZ = (BCC, BCC); BCC = (BDD, BDD); BDD = (BEE, BEE); BEE = (BZZ, BZZ)",
+    );
+    let no_terminals = parse(&input, |s| s.ends_with("AA"), |_| false).unwrap();
+    let parsed = parse(&input, |s| s.ends_with("AA"), |s| s.ends_with('Z')).unwrap();
+    assert_eq!(
+        Cycle {
+            prefix: 1,
+            period: 3,
+            terminals: vec![3]
+        },
+        find_cycle(&parsed.instructions, parsed.starts[0], &parsed.nodes)
+    );
+    assert_eq!(Ok(9), solve(parsed));
+    assert_eq!(
+        Err("the ghost starting at AAA never reaches a terminal node".to_string()),
+        solve(no_terminals)
+    );
+
+    // Ghost C is on a terminal only once, at step 1, and ghost D at every even step and at 3
+    let input = network(
+        "CAA = (CZZ, CZZ); CZZ = (CBB, CBB); CBB = (CBB, CBB); \
+         DAA = (DBB, DBB); DBB = (DZZ, DZZ); DZZ = (DYZ, DYZ); DYZ = (DBB, DBB)",
+    );
+    let parsed = parse(&input, |s| s.ends_with("AA"), |s| s.ends_with('Z')).unwrap();
+    assert_eq!(
+        Ok(2),
+        solve(parse(&input, |s| s == "DAA", |s| s.ends_with('Z')).unwrap())
+    );
+    assert_eq!(
+        Err("the ghosts are never all on terminal nodes at once".to_string()),
+        solve(parsed)
+    );
+
+    // Residues 0 (mod 4) and 2 (mod 6) only meet at 8 (mod 12)
+    let input = network(
+        "EAA = (EBB, EBB); EBB = (ECC, ECC); ECC = (EDD, EDD); EDD = (EZZ, EZZ); EZZ = (EBB, EBB); \
+         FAA = (FBB, FBB); FBB = (FZZ, FZZ); FZZ = (FCC, FCC); FCC = (FDD, FDD); FDD = (FEE, FEE); \
+         FEE = (FGG, FGG); FGG = (FHH, FHH); FHH = (FZZ, FZZ)",
+    );
+    assert_eq!(
+        Ok(8),
+        solve(parse(&input, |s| s.ends_with("AA"), |s| s.ends_with('Z')).unwrap())
+    );
+}
+
+#[test]
+fn network_analysis() {
+    let input = parse(
+        include_str!("example3.txt"),
+        |s| s.ends_with('A'),
+        |s| s.ends_with('Z'),
+    )
+    .unwrap();
+    let mut components = input
+        .components()
+        .into_iter()
+        .map(|c| {
+            let mut names = c.iter().map(|n| input.name(*n)).collect::<Vec<_>>();
+            names.sort_unstable();
+            names
+        })
+        .collect::<Vec<_>>();
+    components.sort_unstable();
+    assert_eq!(
+        vec![
+            vec!["11A"],
+            vec!["11B", "11Z"],
+            vec!["22A"],
+            vec!["22B", "22C", "22Z"],
+            vec!["XXX"]
+        ],
+        components
+    );
+    assert!(input.unreachable_starts().is_empty());
+
+    let mut analysis = Vec::new();
+    input.write_analysis(&mut analysis).unwrap();
+    assert_eq!(
+        "8 nodes in 5 strongly connected components; larger than one node: [3, 2]\n\
+         11A: prefix 1, period 2, terminal once at [], then at [2] and every 2 steps after\n\
+         22A: prefix 1, period 6, terminal once at [], then at [3, 6] and every 6 steps after\n",
+        String::from_utf8(analysis).unwrap()
+    );
+
+    let mut dot = Vec::new();
+    input.write_dot(&mut dot).unwrap();
+    let dot = String::from_utf8(dot).unwrap();
+    assert!(dot.contains("  \"11A\" [shape=circle, style=filled, fillcolor=palegreen];\n"));
+    assert!(dot.contains("  \"11Z\" [shape=doublecircle, style=filled, fillcolor=salmon];\n"));
+    assert!(dot.contains("  \"11A\" -> \"11B\" [label=\"L\"];\n"));
+    assert!(dot.contains("  \"XXX\" -> \"XXX\" [label=\"L/R\"];\n"));
+
+    let stuck = parse(
+        "R\n\nAAA = (BBB, ZZZ)\nBBB = (BBB, BBB)\nZZZ = (ZZZ, ZZZ)\nCCC = (CCC, CCC)",
+        |s| s == "AAA" || s == "CCC",
+        |s| s == "ZZZ",
+    )
+    .unwrap();
+    assert_eq!(
+        vec!["CCC"],
+        stuck
+            .unreachable_starts()
+            .into_iter()
+            .map(|n| stuck.name(n))
+            .collect::<Vec<_>>()
+    );
+
+    // DDD could reach ZZZ by going right, but only ever goes left
+    let astray = parse(
+        "L\n\nDDD = (DDD, ZZZ)\nZZZ = (ZZZ, ZZZ)\nCCC = (CCC, CCC)",
+        |s| s == "DDD" || s == "CCC",
+        |s| s == "ZZZ",
+    )
+    .unwrap();
+    assert_eq!(vec![0, 2], astray.unreachable_starts());
+    let mut analysis = Vec::new();
+    astray.write_analysis(&mut analysis).unwrap();
+    assert!(String::from_utf8(analysis).unwrap().ends_with(
+        "DDD: the instructions never lead to a terminal node\nCCC: no path to a terminal node\n"
+    ));
+}
+
+#[test]
+fn jump_table() {
+    let input = parse(include_str!("example2.txt"), |s| s == "AAA", |s| s == "ZZZ").unwrap();
+    let jumps = Jumps::new(&input.instructions, &input.nodes);
+    assert_eq!(Some(6), jumps.count_steps(input.node("AAA").unwrap()));
+    assert_eq!(None, input.node("CCC"));
+
+    // Every cycle agrees with walking one step at a time, on random networks
+    let mut random = XorShift::new(0x2545_f491_4f6c_dd1d);
+    for _ in 0..50 {
+        let names = (0..2 + random.below(40))
+            .map(|i| format!("{i:03}"))
+            .collect::<Vec<_>>();
+        let instructions = (0..1 + random.below(8))
+            .map(|_| if random.below(2) == 0 { 'L' } else { 'R' })
+            .collect::<String>();
+        let nodes = names
+            .iter()
+            .map(|name| {
+                let left = &names[random.below(names.len())];
+                let right = &names[random.below(names.len())];
+                format!("{name} = ({left}, {right})")
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let network = format!("{instructions}\n\n{nodes}");
+        let input = parse(&network, |s| s == "000", |s| s.ends_with('7')).unwrap();
+        let jumps = Jumps::new(&input.instructions, &input.nodes);
+        let cycle = jumps.cycle(0);
+
+        let mut here = 0;
+        let mut states = Vec::new();
+        for step in 0..cycle.prefix + 2 * cycle.period {
+            let i = step % input.instructions.len();
+            assert_eq!(input.nodes[here].1, cycle.is_terminal_at(step));
+            states.push((here, i));
+            here = next(&input.nodes, here, &input.instructions[i]);
+        }
+        assert_eq!(states[cycle.prefix], states[cycle.prefix + cycle.period]);
+        assert!(
+            cycle.prefix == 0
+                || states[cycle.prefix - 1] != states[cycle.prefix - 1 + cycle.period]
+        );
+        assert!(
+            !states[cycle.prefix + 1..cycle.prefix + cycle.period].contains(&states[cycle.prefix])
+        );
+        assert_eq!(
+            cycle.terminals.first().copied(),
+            jumps.count_steps(0),
+            "{network}"
+        );
+    }
+}
+
+#[test]
+fn combination_limits() {
+    // Coprime periods, on a terminal at every step but the first of each cycle, multiply the
+    // combinations to check: 100 * 102 * 106 of them
+    let name = |c: char, i: usize| {
+        let digit = |d: usize| char::from_digit(d as u32, 36).unwrap().to_ascii_uppercase();
+        format!("{c}{}{}", digit(i / 36), digit(i % 36))
+    };
+    let nodes = [101, 103, 107]
+        .into_iter()
+        .zip('A'..)
+        .flat_map(|(p, c)| {
+            (0..p).map(move |i| {
+                let next = name(c, (i + 1) % p);
+                format!("{} = ({next}, {next})", name(c, i))
+            })
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    let input = format!("L\n\n{nodes}");
+    let input = parse(&input, |s| &s[1..] == "00", |s| &s[1..] != "00").unwrap();
+    assert_eq!(
+        Err(format!(
+            "more than {MAX_RESIDUES} combinations of the ghosts' terminal steps to check"
+        )),
+        solve(input)
+    );
+
+    assert_eq!(Some(Ok((8, 12))), crt(0, 4, 2, 6));
+    assert_eq!(None, crt(0, 4, 1, 6));
+    assert_eq!(
+        Some(Err("the ghosts' cycles are too long to combine".to_string())),
+        crt(0, u128::MAX, 0, 2)
+    );
+    assert_eq!(
+        Some(Err("the ghosts' cycles are too long to combine".to_string())),
+        crt(0, 1 << 110, 0, 1_000_003)
+    );
+}
+
+#[test]
+fn parse_errors() {
+    let error = |input: &str| parse(input, |s| s == "AAA", |s| s == "ZZZ").err();
+    assert_eq!(
+        Some("unknown instruction 'X'".to_string()),
+        error("LX\n\nAAA = (AAA, AAA)")
+    );
+    assert_eq!(
+        Some("expected `<node> = (<left>, <right>)`, found \"AAA = AAA\"".to_string()),
+        error("L\n\nAAA = AAA")
+    );
+    assert_eq!(
+        Some("node name \"TOO LONG!\" does not fit in 8 bytes".to_string()),
+        error("L\n\nAAA = (TOO LONG!, AAA)")
+    );
+    assert_eq!(
+        Some("line 5: node AAA is already defined on line 3".to_string()),
+        error("L\n\nAAA = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)\nAAA = (AAA, AAA)")
+    );
+
+    // Names need not be three letters
+    let input = parse(
+        "L\n\nSTART = (Z, Z)\nZ = (Z, Z)",
+        |s| s == "START",
+        |s| s == "Z",
+    )
+    .unwrap();
+    assert_eq!(Some(1), input.node("Z"));
+    assert_eq!(Ok(1), solve(input));
+
+    // BBB and YYY are only ever destinations, and keep a ghost that reaches them there
+    let network = "L\n\nAAA = (BBB, ZZZ)\nZZZ = (YYY, YYY)";
+    let stuck = parse(network, |s| s == "AAA", |s| s == "ZZZ").unwrap();
+    assert_eq!(
+        vec![
+            ([1, 2], false),
+            ([1, 1], false),
+            ([3, 3], true),
+            ([3, 3], false)
+        ],
+        stuck.nodes
+    );
+    let mut analysis = Vec::new();
+    stuck.write_analysis(&mut analysis).unwrap();
+    assert!(String::from_utf8(analysis)
+        .unwrap()
+        .ends_with("AAA: the instructions never lead to a terminal node\n"));
+    let network = network.replacen('L', "R", 1);
+    assert_eq!(
+        Ok(1),
+        solve(parse(&network, |s| s == "AAA", |s| s == "ZZZ").unwrap())
+    );
+}
@@ -1,20 +1,17 @@
-#![feature(iter_map_windows)]
-
 use std::fmt::Debug;
 use std::str::FromStr;
 
-const INPUT: &str = include_str!("input.txt");
+pub const INPUT: &str = include_str!("input.txt");
 
-fn main() {
-    eprintln!("{}", part1(INPUT));
-    eprintln!("{}", part2(INPUT));
+pub fn part1(input: &str) -> i32 {
+    parse(input).map(predict).sum::<i32>()
 }
 
-fn part1(input: &str) -> i32 {
-    input.lines().map(parse_values).map(predict).sum::<i32>()
+pub fn parse(input: &str) -> impl Iterator<Item = Vec<i32>> + '_ {
+    input.lines().map(parse_values)
 }
 
-fn predict(history: Vec<i32>) -> i32 {
+pub fn predict(history: Vec<i32>) -> i32 {
     let mut n = 0;
     let mut last = history;
     while last.iter().any(|v| *v != 0) {
@@ -39,11 +36,9 @@ where
         .collect()
 }
 
-fn part2(input: &str) -> i32 {
-    input
-        .lines()
-        .map(|l| {
-            let mut v = parse_values(l);
+pub fn part2(input: &str) -> i32 {
+    parse(input)
+        .map(|mut v| {
             v.reverse();
             v
         })
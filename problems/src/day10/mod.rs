@@ -1,26 +1,22 @@
-use std::collections::HashMap;
+use adventofcode2023_common::render::{Canvas, Rgb, Shape, Style};
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::mem::replace;
 use std::ops::Add;
 
-const INPUT: &str = include_str!("input.txt");
+pub const INPUT: &str = include_str!("input.txt");
 
-fn main() {
-    eprintln!("{}", part1(INPUT));
-    eprintln!("{}", part2(INPUT));
-}
-
-struct Input {
-    start: Point,
-    nodes: HashMap<Point, Node<Point>>,
-    exits_at_start: Vec<Point>,
+pub struct Input {
+    pub start: Point,
+    pub nodes: HashMap<Point, Node<Point>>,
+    pub exits_at_start: Vec<Point>,
 }
 
 #[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
-struct Point([i32; 2]);
+pub struct Point(pub [i32; 2]);
 
 #[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
-struct Direction([i32; 2]);
+pub struct Direction(pub [i32; 2]);
 
 impl Add<Direction> for Point {
     type Output = Self;
@@ -40,7 +36,7 @@ const fn d(x: i32, y: i32) -> Direction {
 }
 
 #[derive(Copy, Clone, Debug)]
-enum Node<V> {
+pub enum Node<V> {
     Start,
     Outputs([V; 2]),
 }
@@ -99,14 +95,14 @@ impl Node<Direction> {
     }
 }
 
-fn part1(input: &str) -> usize {
-    let input = parse_input(input);
+pub fn part1(input: &str) -> usize {
+    let input = parse(input);
     let l = find_loop(&input);
     l.len() / 2
 }
 
-fn part2(input: &str) -> i32 {
-    let input = parse_input(input);
+pub fn part2(input: &str) -> i32 {
+    let input = parse(input);
     let path = find_loop(&input);
 
     let mut nodes = path
@@ -125,6 +121,39 @@ fn part2(input: &str) -> i32 {
     inner
 }
 
+pub fn render(input: &str) -> Canvas<impl Fn(usize, usize) -> Style + '_> {
+    let path = find_loop(&parse(input));
+    let on_loop = path.iter().copied().collect::<HashSet<_>>();
+    let polygon = path
+        .iter()
+        .map(|Point([x, y])| (*x as f64 + 0.5, *y as f64 + 0.5))
+        .collect();
+
+    let tiles = input.lines().map(str::as_bytes).collect::<Vec<_>>();
+    let width = tiles.iter().map(|line| line.len()).max().unwrap_or(0);
+    Canvas::new(width, tiles.len(), move |x, y| {
+        let c = tiles[y].get(x).map_or(' ', |c| *c as char);
+        if on_loop.contains(&p(x as i32, y as i32)) {
+            Style::new(pipe_glyph(c), Rgb::WHITE)
+        } else {
+            Style::new(c, Rgb::GREY)
+        }
+    })
+    .overlay(Shape::Polygon(polygon), Rgb::GREEN)
+}
+
+fn pipe_glyph(c: char) -> char {
+    match c {
+        '|' => '│',
+        '-' => '─',
+        'L' => '└',
+        'J' => '┘',
+        '7' => '┐',
+        'F' => '┌',
+        c => c,
+    }
+}
+
 fn inside_space_in_row(y: i32, min_x: i32, max_x: i32, nodes: &HashMap<Point, Node<Point>>) -> i32 {
     let mut inner = 0;
     let mut verticality = Verticality::None;
@@ -156,7 +185,7 @@ fn inside_space_in_row(y: i32, min_x: i32, max_x: i32, nodes: &HashMap<Point, No
     inner
 }
 
-fn find_loop(input: &Input) -> Vec<Point> {
+pub fn find_loop(input: &Input) -> Vec<Point> {
     for node in &input.exits_at_start {
         let path = traverse(input.start, *node, &input.nodes).collect::<Vec<_>>();
         if path.last().is_some_and(|p| *p == input.start) {
@@ -212,7 +241,7 @@ fn parse_line(s: &str) -> impl Iterator<Item = (i32, Node<Direction>)> + '_ {
     })
 }
 
-fn parse_input(input: &str) -> Input {
+pub fn parse(input: &str) -> Input {
     let nodes = input
         .lines()
         .enumerate()
@@ -287,3 +316,14 @@ fn part2_example5() {
 fn part2_verify() {
     assert_eq!(453, part2(INPUT));
 }
+
+#[test]
+fn render_example() {
+    let mut svg = Vec::new();
+    render(include_str!("example3.txt"))
+        .write_svg(&mut svg, 10)
+        .unwrap();
+    let svg = String::from_utf8(svg).unwrap();
+    assert_eq!(1, svg.matches("<polygon").count());
+    assert!(svg.contains('┌'));
+}
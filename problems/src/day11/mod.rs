@@ -1,13 +1,53 @@
+use adventofcode2023_common::render::{Canvas, Rgb, Shape, Style};
+use std::collections::HashSet;
 use std::fmt::Debug;
 
-const INPUT: &str = include_str!("input.txt");
+pub const INPUT: &str = include_str!("input.txt");
 
-fn main() {
-    eprintln!("{}", solve(INPUT, 2));
-    eprintln!("{}", solve(INPUT, 1_000_000));
+pub fn part1(input: &str) -> i64 {
+    solve(input, 2)
 }
 
-fn parse_input(input: &str) -> Vec<Point> {
+pub fn part2(input: &str) -> i64 {
+    solve(input, 1_000_000)
+}
+
+pub fn render(input: &str) -> Canvas<impl Fn(usize, usize) -> Style> {
+    let galaxies = parse(input);
+    let width = input.lines().map(str::len).max().unwrap_or(0);
+    let height = input.lines().count();
+    let occupied_columns = galaxies
+        .iter()
+        .map(|Point([x, _])| *x)
+        .collect::<HashSet<_>>();
+    let occupied_rows = galaxies
+        .iter()
+        .map(|Point([_, y])| *y)
+        .collect::<HashSet<_>>();
+    let at = galaxies.iter().copied().collect::<HashSet<_>>();
+    let centres = galaxies
+        .iter()
+        .map(|Point([x, y])| (*x as f64 + 0.5, *y as f64 + 0.5))
+        .collect();
+
+    Canvas::new(width, height, move |x, y| {
+        let (x, y) = (x as i64, y as i64);
+        let style = if at.contains(&p(x, y)) {
+            Style::new('#', Rgb::WHITE)
+        } else {
+            Style::new('.', Rgb::GREY)
+        };
+        if occupied_columns.contains(&x) && occupied_rows.contains(&y) {
+            style
+        } else {
+            // Rows and columns without galaxies are the ones that expand
+            style.on(Rgb(0, 43, 54))
+        }
+    })
+    .overlay(Shape::Points(centres), Rgb::YELLOW)
+}
+
+pub fn parse(input: &str) -> Vec<Point> {
     input
         .lines()
         .enumerate()
@@ -22,7 +62,7 @@ fn parse_line(line: &str) -> impl Iterator<Item = usize> + '_ {
 }
 
 #[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
-struct Point([i64; 2]);
+pub struct Point(pub [i64; 2]);
 
 const fn p(x: i64, y: i64) -> Point {
     Point([x, y])
@@ -58,8 +98,8 @@ fn axis_distance(iter: impl Iterator<Item = i64>, expansion_factor: i64) -> i64
     sum_absolute_pairwise_differences(values)
 }
 
-fn solve(input: &str, expansion_factor: i64) -> i64 {
-    let input = parse_input(input);
+pub fn solve(input: &str, expansion_factor: i64) -> i64 {
+    let input = parse(input);
     let x = axis_distance(input.iter().map(|Point([x, _])| *x), expansion_factor);
     let y = axis_distance(input.iter().map(|Point([_, y])| *y), expansion_factor);
 
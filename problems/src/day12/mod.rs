@@ -1,18 +1,13 @@
-use crate::Condition::{Damaged, Operational, Unknown};
+use Condition::{Damaged, Operational, Unknown};
 
-const INPUT: &str = include_str!("input.txt");
+pub const INPUT: &str = include_str!("input.txt");
 
-fn main() {
-    eprintln!("{}", part1(INPUT));
-    eprintln!("{}", part2(INPUT));
+pub fn part1(input: &str) -> usize {
+    parse(input).map(memoized_arrangements).sum()
 }
 
-fn part1(input: &str) -> usize {
-    parse_input(input).map(memoized_arrangements).sum()
-}
-
-fn part2(input: &str) -> usize {
-    parse_input(input)
+pub fn part2(input: &str) -> usize {
+    parse(input)
         .map(expand_row)
         .map(memoized_arrangements)
         .sum()
@@ -79,7 +74,7 @@ fn next_placement(springs: &[Condition], damaged_length: usize) -> Option<&[Cond
     }
 }
 
-fn memoized_arrangements(row: Row) -> usize {
+pub fn memoized_arrangements(row: Row) -> usize {
     let mut memo = Memo::new(row.conditions.len(), row.damaged_groups.len());
 
     fn arrangements(memo: &mut Memo, springs: &[Condition], damaged_groups: &[usize]) -> usize {
@@ -96,9 +91,11 @@ fn memoized_arrangements(row: Row) -> usize {
                 let a = next_placement(springs, *group)
                     .map(|next| arrangements(memo, next, remaining_groups))
                     .unwrap_or(0);
-                let b = (springs[0] == Unknown)
-                    .then(|| arrangements(memo, &springs[1..], damaged_groups))
-                    .unwrap_or(0);
+                let b = if springs[0] == Unknown {
+                    arrangements(memo, &springs[1..], damaged_groups)
+                } else {
+                    0
+                };
                 a + b
             }
             _ => 0,
@@ -111,7 +108,7 @@ fn memoized_arrangements(row: Row) -> usize {
     arrangements(&mut memo, &row.conditions, &row.damaged_groups)
 }
 
-fn expand_row(mut row: Row) -> Row {
+pub fn expand_row(mut row: Row) -> Row {
     row.conditions.push(Unknown);
     let mut conditions = row.conditions.repeat(5);
     conditions.pop();
@@ -126,22 +123,22 @@ fn expand_row(mut row: Row) -> Row {
 
 #[derive(Copy, Clone, PartialEq)]
 #[repr(u8)]
-enum Condition {
+pub enum Condition {
     Operational,
     Damaged,
     Unknown,
 }
 
-struct Row {
-    conditions: Vec<Condition>,
-    damaged_groups: Vec<usize>,
+pub struct Row {
+    pub conditions: Vec<Condition>,
+    pub damaged_groups: Vec<usize>,
 }
 
-fn parse_input(input: &str) -> impl Iterator<Item = Row> + '_ {
+pub fn parse(input: &str) -> impl Iterator<Item = Row> + '_ {
     input.lines().map(parse_line)
 }
 
-fn parse_line(line: &str) -> Row {
+pub fn parse_line(line: &str) -> Row {
     let (springs, groups) = line.split_once(' ').expect("springs and groups");
     let conditions = springs
         .chars()
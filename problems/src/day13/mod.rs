@@ -0,0 +1,205 @@
+use adventofcode2023_common::bitgrid::BitGrid;
+use adventofcode2023_common::render::{Canvas, Rgb, Shape, Style};
+use std::cmp::min;
+use std::collections::HashSet;
+use Reflection::{Horizontal, Vertical};
+
+pub const INPUT: &str = include_str!("input.txt");
+
+pub fn part1(input: &str) -> usize {
+    parse(input)
+        .flat_map(Pattern::mirrored_positions)
+        .map(Reflection::to_summary)
+        .sum()
+}
+
+pub fn part2(input: &str) -> usize {
+    parse(input)
+        .map(Pattern::find_smudged)
+        .map(Reflection::to_summary)
+        .sum()
+}
+
+// Patterns are drawn one below the other, the way they appear in the input, with the original
+// reflection in green and the one found after fixing the smudge in red.
+pub fn render(input: &str) -> Canvas<impl Fn(usize, usize) -> Style + '_> {
+    let mut y0 = 0;
+    let mut lines = Vec::new();
+    for pattern in parse(input) {
+        let (width, height) = (pattern.by_row.width(), pattern.by_row.height());
+        let reflections = pattern
+            .clone()
+            .mirrored_positions()
+            .map(|r| (r, Rgb::GREEN));
+        let smudged = std::iter::once((pattern.find_smudged(), Rgb::RED));
+        for (reflection, colour) in reflections.chain(smudged) {
+            let line = match reflection {
+                Horizontal(x) => vec![(x as f64, y0 as f64), (x as f64, (y0 + height) as f64)],
+                Vertical(y) => vec![(0.0, (y0 + y) as f64), (width as f64, (y0 + y) as f64)],
+            };
+            lines.push((line, colour));
+        }
+        y0 += height + 1;
+    }
+
+    let tiles = input.lines().map(str::as_bytes).collect::<Vec<_>>();
+    let width = tiles.iter().map(|line| line.len()).max().unwrap_or(0);
+    let canvas = Canvas::new(width, tiles.len(), move |x, y| match tiles[y].get(x) {
+        Some(b'#') => Style::new('#', Rgb::WHITE),
+        Some(_) => Style::new('.', Rgb::GREY),
+        None => Style::new(' ', Rgb::GREY),
+    });
+    lines.into_iter().fold(canvas, |canvas, (line, colour)| {
+        canvas.overlay(Shape::Path(line), colour)
+    })
+}
+
+#[derive(Clone)]
+pub struct Pattern {
+    by_row: BitGrid,
+    by_column: BitGrid,
+}
+
+impl Pattern {
+    fn each_position(&self) -> impl Iterator<Item = (usize, usize)> + 'static {
+        let width = self.by_row.width();
+        let height = self.by_row.height();
+        (0..height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    #[inline]
+    fn smudge_position(mut self, x: usize, y: usize) -> Self {
+        self.by_row.toggle(x, y);
+        self.by_column.toggle(y, x);
+        self
+    }
+
+    fn smudge_each(self) -> impl Iterator<Item = Self> + 'static {
+        self.each_position()
+            .map(move |(x, y)| self.clone().smudge_position(x, y))
+    }
+
+    pub fn mirrored_positions(self) -> impl Iterator<Item = Reflection> + 'static {
+        let h = (1..self.by_column.height())
+            .filter(move |i| is_mirrored_at(*i, &self.by_column))
+            .map(Horizontal);
+        let v = (1..self.by_row.height())
+            .filter(move |i| is_mirrored_at(*i, &self.by_row))
+            .map(Vertical);
+
+        h.chain(v)
+    }
+
+    pub fn find_smudged(self) -> Reflection {
+        let original_positions = self.clone().mirrored_positions().collect::<HashSet<_>>();
+
+        self.smudge_each()
+            .flat_map(Pattern::mirrored_positions)
+            .find(|r| !original_positions.contains(r))
+            .unwrap()
+    }
+}
+
+#[inline]
+fn is_mirrored_at(position: usize, tiles: &BitGrid) -> bool {
+    let len = min(position, tiles.height() - position);
+
+    (position - len..position)
+        .map(|i| tiles.row(i))
+        .eq((position..position + len).rev().map(|i| tiles.row(i)))
+}
+
+#[derive(Copy, Clone, Hash, Eq, PartialEq)]
+pub enum Reflection {
+    Horizontal(usize),
+    Vertical(usize),
+}
+
+impl Reflection {
+    pub fn to_summary(self) -> usize {
+        match self {
+            Horizontal(i) => i,
+            Vertical(i) => i * 100,
+        }
+    }
+}
+
+pub fn parse(input: &str) -> impl Iterator<Item = Pattern> + '_ {
+    input.split("\n\n").map(parse_pattern)
+}
+
+pub fn parse_pattern(input: &str) -> Pattern {
+    let input = input.trim();
+    let width = input.chars().position(|c| c == '\n').expect("first line");
+    let height = input.len() / (width + 1) + 1;
+
+    let mut by_row = BitGrid::new(width, height);
+    for (y, line) in input.lines().enumerate() {
+        for (x, c) in line.chars().enumerate() {
+            let c = match c {
+                '#' => true,
+                '.' => false,
+                _ => unreachable!("invalid input"),
+            };
+
+            by_row.set(x, y, c);
+        }
+    }
+    let by_column = by_row.transpose();
+    Pattern { by_column, by_row }
+}
+
+#[test]
+fn part1_example() {
+    assert_eq!(405, part1(include_str!("example.txt")));
+}
+
+#[ignore]
+#[test]
+fn part1_verify() {
+    assert_eq!(34_889, part1(INPUT));
+}
+
+#[test]
+fn part2_example() {
+    assert_eq!(400, part2(include_str!("example.txt")));
+}
+
+#[ignore]
+#[test]
+fn part2_verify() {
+    assert_eq!(34_224, part2(INPUT));
+}
+
+#[test]
+fn large_pattern() {
+    // Wider and taller than a single machine word, mirrored between columns 39 and 40 and
+    // between rows 69 and 70
+    let pattern = (0..140)
+        .map(|y: usize| {
+            (0..80)
+                .map(|x: usize| {
+                    let (x, y) = (x.min(79 - x), y.min(139 - y));
+                    if (x * 7 + y * 3) % 5 == 0 || x == y {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    assert_eq!(40 + 7000, part1(&pattern));
+}
+
+#[test]
+fn render_example() {
+    let mut svg = Vec::new();
+    render(include_str!("example.txt"))
+        .write_svg(&mut svg, 10)
+        .unwrap();
+    let svg = String::from_utf8(svg).unwrap();
+    assert_eq!(4, svg.matches("<polyline").count());
+}
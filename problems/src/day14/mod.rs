@@ -1,17 +1,11 @@
-#![feature(map_try_insert)]
-
-use crate::Rock::{Cubic, Round};
+use adventofcode2023_common::render::{Canvas, Rgb, Style};
 use std::collections::HashMap;
 use std::fmt::{Debug, Formatter, Write};
+use Rock::{Cubic, Round};
 
-const INPUT: &str = include_str!("input.txt");
-
-fn main() {
-    eprintln!("{}", part1(INPUT));
-    eprintln!("{}", part2(INPUT));
-}
+pub const INPUT: &str = include_str!("input.txt");
 
-fn part1(input: &str) -> usize {
+pub fn part1(input: &str) -> usize {
     let input = input.trim();
     let width = input.chars().position(|c| c == '\n').expect("first line");
     let height = input.len() / (width + 1) + 1;
@@ -33,8 +27,8 @@ fn part1(input: &str) -> usize {
     weights.into_iter().sum()
 }
 
-fn part2(input: &str) -> usize {
-    let mut platform = parse_input(input);
+pub fn part2(input: &str) -> usize {
+    let mut platform = parse(input);
 
     let mut cycle_detector = HashMap::new();
 
@@ -56,9 +50,22 @@ fn part2(input: &str) -> usize {
     platform.load_north()
 }
 
+pub fn render(input: &str) -> Canvas<impl Fn(usize, usize) -> Style> {
+    let platform = parse(input);
+    Canvas::new(
+        platform.width,
+        platform.height,
+        move |x, y| match platform.rocks[x + y * platform.width] {
+            Some(Round) => Style::new('O', Rgb::YELLOW),
+            Some(Cubic) => Style::new('#', Rgb::WHITE).on(Rgb::GREY),
+            None => Style::new('.', Rgb::GREY),
+        },
+    )
+}
+
 #[derive(Copy, Clone, Hash, Eq, PartialEq)]
 #[repr(u8)]
-enum Rock {
+pub enum Rock {
     Round,
     Cubic,
 }
@@ -73,7 +80,7 @@ impl Debug for Rock {
 }
 
 #[derive(Clone, Hash, Eq, PartialEq)]
-struct Platform {
+pub struct Platform {
     width: usize,
     height: usize,
     rocks: Vec<Option<Rock>>,
@@ -96,10 +103,10 @@ impl Debug for Platform {
 }
 
 impl Platform {
-    fn tilt_cycle(self) -> Self {
+    pub fn tilt_cycle(self) -> Self {
         self.tilt_north().tilt_west().tilt_south().tilt_east()
     }
-    fn tilt_north(mut self) -> Self {
+    pub fn tilt_north(mut self) -> Self {
         let mut next_free = (0..self.width).collect::<Vec<_>>();
         for y in 0..self.height {
             let yo = y * self.width;
@@ -121,7 +128,7 @@ impl Platform {
         self
     }
 
-    fn tilt_east(mut self) -> Self {
+    pub fn tilt_east(mut self) -> Self {
         let mut next_free = (0..self.height)
             .map(|y| y * self.width + self.width - 1)
             .collect::<Vec<_>>();
@@ -144,7 +151,7 @@ impl Platform {
         self
     }
 
-    fn tilt_south(mut self) -> Self {
+    pub fn tilt_south(mut self) -> Self {
         let mut next_free = (0..self.width)
             .map(|x| self.width * self.height - self.width + x)
             .collect::<Vec<_>>();
@@ -169,7 +176,7 @@ impl Platform {
         self
     }
 
-    fn tilt_west(mut self) -> Self {
+    pub fn tilt_west(mut self) -> Self {
         let mut next_free = (0..self.height).map(|y| y * self.width).collect::<Vec<_>>();
         for x in 0..self.width {
             for (y, next_free) in next_free.iter_mut().enumerate() {
@@ -190,7 +197,7 @@ impl Platform {
         self
     }
 
-    fn load_north(&self) -> usize {
+    pub fn load_north(&self) -> usize {
         self.rocks
             .iter()
             .enumerate()
@@ -203,7 +210,7 @@ impl Platform {
     }
 }
 
-fn parse_input(input: &str) -> Platform {
+pub fn parse(input: &str) -> Platform {
     let input = input.trim();
     let width = input.chars().position(|c| c == '\n').expect("first line");
     let height = input.len() / (width + 1) + 1;
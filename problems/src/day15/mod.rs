@@ -1,24 +1,23 @@
 use std::array::from_fn;
 
-const INPUT: &str = include_str!("input.txt");
-
-fn main() {
-    eprintln!("{}", part1(INPUT));
-    eprintln!("{}", part2(INPUT));
-}
+pub const INPUT: &str = include_str!("input.txt");
 
 #[inline]
-fn hash(s: &str) -> usize {
+pub fn hash(s: &str) -> usize {
     s.as_bytes()
         .iter()
         .fold(0, |h, c| ((h + *c as usize) * 17) & 0xff)
 }
 
-fn part1(input: &str) -> usize {
+pub fn part1(input: &str) -> usize {
     input.trim().split(',').map(hash).sum()
 }
 
-fn parse_op(v: &str) -> (&str, Option<usize>) {
+pub fn parse(input: &str) -> impl Iterator<Item = (&str, Option<usize>)> + '_ {
+    input.trim().split(',').map(parse_op)
+}
+
+pub fn parse_op(v: &str) -> (&str, Option<usize>) {
     let p = v.find(|c: char| !c.is_ascii_alphabetic()).unwrap();
     let label = &v[..p];
     match &v[p..p + 1] {
@@ -28,7 +27,7 @@ fn parse_op(v: &str) -> (&str, Option<usize>) {
     }
 }
 
-struct LensBox<'a>(Vec<(&'a str, usize)>);
+pub struct LensBox<'a>(Vec<(&'a str, usize)>);
 impl<'a> LensBox<'a> {
     fn new() -> Self {
         Self(Vec::new())
@@ -54,24 +53,30 @@ impl<'a> LensBox<'a> {
     }
 }
 
-struct LensMap<'a> {
+pub struct LensMap<'a> {
     boxes: [LensBox<'a>; 256],
 }
 
+impl<'a> Default for LensMap<'a> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<'a> LensMap<'a> {
-    fn new() -> Self {
+    pub fn new() -> Self {
         Self {
             boxes: from_fn(|_| LensBox::new()),
         }
     }
 
     #[inline]
-    fn insert(&mut self, label: &'a str, focal_length: usize) {
+    pub fn insert(&mut self, label: &'a str, focal_length: usize) {
         self.boxes[hash(label)].insert(label, focal_length)
     }
 
     #[inline]
-    fn remove(&mut self, label: &'a str) {
+    pub fn remove(&mut self, label: &'a str) {
         self.boxes[hash(label)].remove(label)
     }
 }
@@ -96,9 +101,9 @@ impl<'a> IntoIterator for LensMap<'a> {
     }
 }
 
-fn part2(input: &str) -> usize {
+pub fn part2(input: &str) -> usize {
     let mut lenses = LensMap::new();
-    for (label, op) in input.trim().split(',').map(parse_op) {
+    for (label, op) in parse(input) {
         match op {
             None => lenses.remove(label),
             Some(focal_length) => lenses.insert(label, focal_length),